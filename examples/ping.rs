@@ -18,13 +18,22 @@ fn main() {
                             tokio::spawn(
                                 client
                                     .send_message(
-                                        &noob::MessageBuilder::new("pong"),
-                                        &msg.channel_id,
+                                        &noob::MessageBuilder::new("pong")
+                                            .with_reply(msg.id, false),
+                                        msg.channel_id,
                                     )
                                     .map_err(|e| {
                                         eprintln!("{:?}", e);
                                     }),
                             );
+                        } else if msg.content == "dm me" && !msg.is_dm() {
+                            tokio::spawn(
+                                client
+                                    .send_dm(msg.author.id, &noob::MessageBuilder::new("hi"))
+                                    .map_err(|e| {
+                                        eprintln!("{:?}", e);
+                                    }),
+                            );
                         }
                     }
                     Ok(())
@@ -0,0 +1,30 @@
+extern crate futures;
+extern crate noob;
+extern crate tokio;
+
+use futures::Future;
+
+fn main() {
+    let url = std::env::var("WEBHOOK_URL").expect("Missing WEBHOOK_URL");
+    let webhook = noob::WebhookClient::from_url(&url).unwrap();
+
+    let message = noob::MessageBuilder::new("Build finished");
+    let embed = noob::EmbedBuilder::new()
+        .with_title("CI")
+        .with_color(0x43b581);
+
+    tokio::run(
+        webhook
+            .execute_and_wait(
+                &noob::WebhookMessageBuilder::new(message)
+                    .with_username("CI")
+                    .with_embed(embed),
+            )
+            .map(|msg| {
+                println!("sent {}", msg.id);
+            })
+            .map_err(|e| {
+                eprintln!("{:?}", e);
+            }),
+    );
+}
@@ -1,45 +1,185 @@
+use events::{
+    AuditLogEvent, ChannelId, ChannelType, MessageId, MessageReference, PermissionOverwrite, RoleId,
+    Snowflake, Timestamp, UserId,
+};
+use error::LimitError;
 use Error;
 
+use serde;
 use serde_json;
+use std;
+use url;
 
+/// Maximum number of characters in a message's content
+pub const MAX_CONTENT_LENGTH: usize = 2000;
+const MAX_EMBED_TITLE_LENGTH: usize = 256;
+const MAX_EMBED_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_EMBED_FIELDS: usize = 25;
+const MAX_EMBED_FIELD_NAME_LENGTH: usize = 256;
+const MAX_EMBED_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_EMBED_FOOTER_LENGTH: usize = 2048;
+const MAX_EMBED_AUTHOR_NAME_LENGTH: usize = 256;
+const MAX_EMBED_TOTAL_LENGTH: usize = 6000;
+const MAX_WEBHOOK_EMBEDS: usize = 10;
+
+fn check_limit(field: &str, limit: usize, actual: usize) -> Result<(), Error> {
+    if actual > limit {
+        Err(Error::LimitExceeded(LimitError {
+            field: field.to_owned(),
+            limit,
+            actual,
+        }))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks the length of a text field, returning its length in characters
+fn check_length(field: &str, limit: usize, value: &str) -> Result<usize, Error> {
+    let length = value.chars().count();
+    check_limit(field, limit, length)?;
+    Ok(length)
+}
+
+#[derive(Clone, Debug, Default)]
 /// Object used to construct outgoing messages
-pub struct MessageBuilder<'a> {
-    content: &'a str,
-    embed: Option<&'a EmbedBuilder<'a>>,
+pub struct MessageBuilder {
+    content: String,
+    embed: Option<EmbedBuilder>,
+    message_reference: Option<MessageReference>,
+    allowed_mentions: Option<AllowedMentions>,
+    mention_replied_user: Option<bool>,
 }
 
-impl<'a> MessageBuilder<'a> {
+impl MessageBuilder {
     /// Create a new MessageBuilder
-    pub fn new(content: &'a str) -> Self {
+    pub fn new<S: Into<String>>(content: S) -> Self {
         Self {
-            content,
+            content: content.into(),
             embed: None,
+            message_reference: None,
+            allowed_mentions: None,
+            mention_replied_user: None,
         }
     }
 
     /// Set an embed for this message
-    pub fn set_embed(&mut self, embed: &'a EmbedBuilder<'a>) {
+    pub fn set_embed(&mut self, embed: EmbedBuilder) {
         self.embed = Some(embed);
     }
 
     /// Set an embed for this message
-    pub fn with_embed(mut self, embed: &'a EmbedBuilder<'a>) -> Self {
+    pub fn with_embed(mut self, embed: EmbedBuilder) -> Self {
         self.set_embed(embed);
         self
     }
 
+    /// Send this message as a reply to another message in the same channel
+    ///
+    /// If `fail_if_not_exists` is false, the message is sent without a reply if the referenced message was deleted.
+    pub fn set_reply(&mut self, message_id: MessageId, fail_if_not_exists: bool) {
+        self.message_reference = Some(MessageReference {
+            message_id: Some(message_id),
+            channel_id: None,
+            guild_id: None,
+            fail_if_not_exists: Some(fail_if_not_exists),
+        });
+    }
+
+    /// Send this message as a reply to another message in the same channel
+    ///
+    /// If `fail_if_not_exists` is false, the message is sent without a reply if the referenced message was deleted.
+    pub fn with_reply(mut self, message_id: MessageId, fail_if_not_exists: bool) -> Self {
+        self.set_reply(message_id, fail_if_not_exists);
+        self
+    }
+
+    /// Set which mentions in this message may ping, overriding the client's default
+    pub fn set_allowed_mentions(&mut self, allowed_mentions: AllowedMentions) {
+        self.allowed_mentions = Some(allowed_mentions);
+    }
+
+    /// Set which mentions in this message may ping, overriding the client's default
+    pub fn with_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.set_allowed_mentions(allowed_mentions);
+        self
+    }
+
+    /// Set whether a reply pings the author of the referenced message. Discord defaults to true.
+    pub fn set_mention_replied_user(&mut self, mention: bool) {
+        self.mention_replied_user = Some(mention);
+    }
+
+    /// Set whether a reply pings the author of the referenced message. Discord defaults to true.
+    pub fn with_mention_replied_user(mut self, mention: bool) -> Self {
+        self.set_mention_replied_user(mention);
+        self
+    }
+
+    /// Split this message into several that each fit within [`MAX_CONTENT_LENGTH`](constant.MAX_CONTENT_LENGTH.html)
+    ///
+    /// See [`split_content`](fn.split_content.html) for how the content is divided. The reply
+    /// reference stays on the first message and the embed moves to the last one.
+    pub fn split(&self) -> Vec<MessageBuilder> {
+        let chunks = split_content(&self.content, MAX_CONTENT_LENGTH);
+        if chunks.len() <= 1 {
+            return vec![self.clone()];
+        }
+        let last = chunks.len() - 1;
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, content)| MessageBuilder {
+                content,
+                embed: if i == last { self.embed.clone() } else { None },
+                message_reference: if i == 0 {
+                    self.message_reference.clone()
+                } else {
+                    None
+                },
+                allowed_mentions: self.allowed_mentions.clone(),
+                mention_replied_user: if i == 0 {
+                    self.mention_replied_user
+                } else {
+                    None
+                },
+            })
+            .collect()
+    }
+
+    fn resolve_allowed_mentions(&self, default: &AllowedMentions) -> AllowedMentions {
+        let mut allowed_mentions = self.allowed_mentions.as_ref().unwrap_or(default).clone();
+        if let Some(replied_user) = self.mention_replied_user {
+            allowed_mentions.set_replied_user(replied_user);
+        }
+        allowed_mentions
+    }
+
+    /// Check this message against Discord's documented length limits
+    pub fn validate(&self) -> Result<(), Error> {
+        check_length("content", MAX_CONTENT_LENGTH, &self.content)?;
+        match self.embed {
+            Some(ref embed) => embed.validate(),
+            None => Ok(()),
+        }
+    }
+
     #[doc(hidden)]
-    pub fn to_request_body(&self, channel: &str) -> Result<String, Error> {
+    pub fn to_request_body(&self, default_allowed_mentions: &AllowedMentions) -> Result<String, Error> {
+        self.validate()?;
         #[derive(Serialize, Debug)]
         struct MessageCreateBody<'a> {
             content: &'a str,
-            channel: &'a str,
-            embed: Option<&'a EmbedBuilder<'a>>,
+            embed: Option<&'a EmbedBuilder>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            message_reference: Option<&'a MessageReference>,
+            allowed_mentions: AllowedMentions,
         }
         serde_json::to_string(&MessageCreateBody {
-            content: self.content,
-            channel,
-            embed: self.embed,
+            content: &self.content,
+            embed: self.embed.as_ref(),
+            message_reference: self.message_reference.as_ref(),
+            allowed_mentions: self.resolve_allowed_mentions(default_allowed_mentions),
         }).map_err(|e| {
             Error::Other(format!(
                 "Failed to serialize message creation body: {:?}",
@@ -49,41 +189,470 @@ impl<'a> MessageBuilder<'a> {
     }
 }
 
-#[derive(Default, Serialize, Debug)]
-/// Builder for a message embed
-pub struct EmbedBuilder<'a> {
-    title: Option<&'a str>,
-    description: Option<&'a str>,
-    url: Option<&'a str>,
-    timestamp: Option<&'a str>,
+/// Split text into chunks of at most `limit` characters
+///
+/// Text is split at line breaks where possible, then at spaces, and only mid-word as a last resort.
+/// If a chunk ends inside a code block, the block is closed and reopened with the same language
+/// at the start of the next chunk.
+pub fn split_content(content: &str, limit: usize) -> Vec<String> {
+    let mut splitter = ContentSplitter {
+        limit,
+        chunks: Vec::new(),
+        current: String::new(),
+        current_len: 0,
+        has_lines: false,
+        fence: None,
+        opening_fence: None,
+    };
+    for line in content.split('\n') {
+        if !splitter.fits(line) {
+            splitter.finish_chunk();
+        }
+        splitter.push_long_line(line);
+    }
+    // Nothing follows, so an empty trailing code block is kept rather than carried over
+    splitter.opening_fence = None;
+    splitter.finish_chunk();
+    splitter.chunks
+}
+
+const CLOSING_FENCE: &str = "\n```";
+
+struct ContentSplitter {
+    limit: usize,
+    chunks: Vec<String>,
+    current: String,
+    current_len: usize,
+    has_lines: bool,
+    /// Language of the code block open at the end of `current`, if any
+    fence: Option<String>,
+    /// Byte offset in `current` of the fence opening the code block, if the block is still empty
+    opening_fence: Option<usize>,
+}
+
+impl ContentSplitter {
+    /// Space to keep free for closing the code block open before or after `line`
+    fn reserve(&self, line: &str) -> usize {
+        if self.fence.is_some() || fence_after_line(&self.fence, line).is_some() {
+            CLOSING_FENCE.len()
+        } else {
+            0
+        }
+    }
+
+    fn fits(&self, line: &str) -> bool {
+        let separator = if self.has_lines { 1 } else { 0 };
+        self.current_len + separator + line.chars().count() + self.reserve(line) <= self.limit
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let fence_after = fence_after_line(&self.fence, line);
+        if self.has_lines {
+            self.current.push('\n');
+            self.current_len += 1;
+        }
+        self.opening_fence = match (&self.fence, &fence_after, line.rfind("```")) {
+            (None, Some(language), Some(start)) if line[start + 3..].trim() == language => {
+                Some(self.current.len() + start)
+            }
+            _ => None,
+        };
+        self.current.push_str(line);
+        self.current_len += line.chars().count();
+        self.has_lines = true;
+        self.fence = fence_after;
+    }
+
+    /// Adds a line, splitting it across chunks if it doesn't fit in one
+    fn push_long_line(&mut self, line: &str) {
+        let mut rest = line;
+        while !self.fits(rest) {
+            let available = self
+                .limit
+                .saturating_sub(self.current_len + self.reserve(rest))
+                .max(1);
+            let (piece, remainder) = split_piece(rest, available);
+            self.push_line(piece);
+            if remainder.is_empty() {
+                return;
+            }
+            self.finish_chunk();
+            rest = remainder;
+        }
+        self.push_line(rest);
+    }
+
+    fn finish_chunk(&mut self) {
+        if !self.has_lines {
+            return;
+        }
+        let mut chunk = std::mem::take(&mut self.current);
+        if let Some(start) = self.opening_fence.take() {
+            // The code block has no content yet, so move its opening fence to the next chunk
+            self.current = chunk.split_off(start);
+            self.current.push('\n');
+            if chunk.ends_with('\n') {
+                chunk.pop();
+            }
+        } else if let Some(ref language) = self.fence {
+            chunk.push_str(CLOSING_FENCE);
+            self.current = format!("```{}\n", language);
+        }
+        self.current_len = self.current.chars().count();
+        self.has_lines = false;
+        if !chunk.trim().is_empty() {
+            self.chunks.push(chunk);
+        }
+    }
+}
+
+/// Returns the language of the code block open after `line`, given the one open before it
+fn fence_after_line(fence: &Option<String>, line: &str) -> Option<String> {
+    if line.matches("```").count().is_multiple_of(2) {
+        return fence.clone();
+    }
+    match *fence {
+        Some(_) => None,
+        None => {
+            let after = &line[line.rfind("```").unwrap() + 3..];
+            Some(after.split_whitespace().next().unwrap_or("").to_owned())
+        }
+    }
+}
+
+/// Splits off the start of a line, of at most `max` characters, preferring to break at a space
+fn split_piece(line: &str, max: usize) -> (&str, &str) {
+    let hard_end = match line.char_indices().nth(max) {
+        Some((hard_end, _)) => hard_end,
+        None => return (line, ""),
+    };
+    let (end, next) = if line[hard_end..].starts_with(' ') {
+        (hard_end, hard_end + 1)
+    } else {
+        match line[..hard_end].rfind(' ') {
+            Some(i) if i > 0 => (i, i + 1),
+            _ => {
+                // Never break up a run of backticks, which could be a code fence
+                let run_start = line[..hard_end].trim_end_matches('`').len();
+                if run_start > 0 && line[hard_end..].starts_with('`') {
+                    (run_start, run_start)
+                } else {
+                    (hard_end, hard_end)
+                }
+            }
+        }
+    };
+    (&line[..end], &line[next..])
+}
+
+#[derive(Clone, Debug)]
+/// Controls which mentions in a message actually notify anyone. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#allowed-mentions-object))
+///
+/// Mentions not allowed here still render, but don't ping.
+/// The [default](#impl-Default) allows users and roles to ping, but not `@everyone` or `@here`.
+pub struct AllowedMentions {
+    parse_users: bool,
+    parse_roles: bool,
+    parse_everyone: bool,
+    users: Vec<UserId>,
+    roles: Vec<RoleId>,
+    replied_user: Option<bool>,
+}
+
+impl AllowedMentions {
+    /// Allow every mention in the content to ping, like Discord does by default
+    pub fn all() -> Self {
+        AllowedMentions {
+            parse_users: true,
+            parse_roles: true,
+            parse_everyone: true,
+            users: Vec::new(),
+            roles: Vec::new(),
+            replied_user: None,
+        }
+    }
+
+    /// Don't allow any mention to ping
+    pub fn none() -> Self {
+        AllowedMentions {
+            parse_users: false,
+            parse_roles: false,
+            parse_everyone: false,
+            users: Vec::new(),
+            roles: Vec::new(),
+            replied_user: None,
+        }
+    }
+
+    /// Set whether any user mentioned in the content may be pinged
+    pub fn set_parse_users(&mut self, parse: bool) {
+        self.parse_users = parse;
+    }
+
+    /// Set whether any user mentioned in the content may be pinged
+    pub fn with_parse_users(mut self, parse: bool) -> Self {
+        self.set_parse_users(parse);
+        self
+    }
+
+    /// Set whether any role mentioned in the content may be pinged
+    pub fn set_parse_roles(&mut self, parse: bool) {
+        self.parse_roles = parse;
+    }
+
+    /// Set whether any role mentioned in the content may be pinged
+    pub fn with_parse_roles(mut self, parse: bool) -> Self {
+        self.set_parse_roles(parse);
+        self
+    }
+
+    /// Set whether `@everyone` and `@here` may ping
+    pub fn set_parse_everyone(&mut self, parse: bool) {
+        self.parse_everyone = parse;
+    }
+
+    /// Set whether `@everyone` and `@here` may ping
+    pub fn with_parse_everyone(mut self, parse: bool) -> Self {
+        self.set_parse_everyone(parse);
+        self
+    }
+
+    /// Allow a specific user to be pinged. Takes precedence over [`set_parse_users`](#method.set_parse_users).
+    pub fn add_user(&mut self, user_id: UserId) {
+        self.users.push(user_id);
+    }
+
+    /// Allow a specific user to be pinged. Takes precedence over [`set_parse_users`](#method.set_parse_users).
+    pub fn with_user(mut self, user_id: UserId) -> Self {
+        self.add_user(user_id);
+        self
+    }
+
+    /// Allow a specific role to be pinged. Takes precedence over [`set_parse_roles`](#method.set_parse_roles).
+    pub fn add_role(&mut self, role_id: RoleId) {
+        self.roles.push(role_id);
+    }
+
+    /// Allow a specific role to be pinged. Takes precedence over [`set_parse_roles`](#method.set_parse_roles).
+    pub fn with_role(mut self, role_id: RoleId) -> Self {
+        self.add_role(role_id);
+        self
+    }
+
+    /// Set whether a reply pings the author of the referenced message
+    pub fn set_replied_user(&mut self, replied_user: bool) {
+        self.replied_user = Some(replied_user);
+    }
+
+    /// Set whether a reply pings the author of the referenced message
+    pub fn with_replied_user(mut self, replied_user: bool) -> Self {
+        self.set_replied_user(replied_user);
+        self
+    }
+}
+
+impl Default for AllowedMentions {
+    /// Allow user and role mentions to ping, but not `@everyone` or `@here`
+    fn default() -> Self {
+        AllowedMentions::all().with_parse_everyone(false)
+    }
+}
+
+impl serde::Serialize for AllowedMentions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct AllowedMentionsBody<'a> {
+            parse: Vec<&'static str>,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            users: &'a [UserId],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            roles: &'a [RoleId],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            replied_user: Option<bool>,
+        }
+        // Discord rejects a type in `parse` that also has an explicit list
+        let mut parse = Vec::new();
+        if self.parse_users && self.users.is_empty() {
+            parse.push("users");
+        }
+        if self.parse_roles && self.roles.is_empty() {
+            parse.push("roles");
+        }
+        if self.parse_everyone {
+            parse.push("everyone");
+        }
+        serde::Serialize::serialize(
+            &AllowedMentionsBody {
+                parse,
+                users: &self.users,
+                roles: &self.roles,
+                replied_user: self.replied_user,
+            },
+            serializer,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Object used to construct messages sent through a webhook
+pub struct WebhookMessageBuilder {
+    message: MessageBuilder,
+    username: Option<String>,
+    avatar_url: Option<String>,
+    embeds: Vec<EmbedBuilder>,
+}
+
+impl WebhookMessageBuilder {
+    /// Create a new WebhookMessageBuilder from a message
+    pub fn new(message: MessageBuilder) -> Self {
+        Self {
+            message,
+            username: None,
+            avatar_url: None,
+            embeds: Vec::new(),
+        }
+    }
+
+    /// Override the webhook's default username
+    pub fn set_username<S: Into<String>>(&mut self, username: S) {
+        self.username = Some(username.into());
+    }
+
+    /// Override the webhook's default username
+    pub fn with_username<S: Into<String>>(mut self, username: S) -> Self {
+        self.set_username(username);
+        self
+    }
+
+    /// Override the webhook's default avatar
+    pub fn set_avatar_url<S: Into<String>>(&mut self, avatar_url: S) {
+        self.avatar_url = Some(avatar_url.into());
+    }
+
+    /// Override the webhook's default avatar
+    pub fn with_avatar_url<S: Into<String>>(mut self, avatar_url: S) -> Self {
+        self.set_avatar_url(avatar_url);
+        self
+    }
+
+    /// Add an embed after the message's own embed, up to 10 in total
+    pub fn add_embed(&mut self, embed: EmbedBuilder) {
+        self.embeds.push(embed);
+    }
+
+    /// Add an embed after the message's own embed, up to 10 in total
+    pub fn with_embed(mut self, embed: EmbedBuilder) -> Self {
+        self.add_embed(embed);
+        self
+    }
+
+    /// Check this message against Discord's documented length limits
+    pub fn validate(&self) -> Result<(), Error> {
+        check_length("content", MAX_CONTENT_LENGTH, &self.message.content)?;
+        let embeds: Vec<_> = self.message.embed.iter().chain(self.embeds.iter()).collect();
+        check_limit("embeds", MAX_WEBHOOK_EMBEDS, embeds.len())?;
+        let mut total = 0;
+        for (i, embed) in embeds.iter().enumerate() {
+            total += embed.validate_at(&format!("embeds[{}]", i))?;
+        }
+        check_limit("embeds", MAX_EMBED_TOTAL_LENGTH, total)
+    }
+
+    #[doc(hidden)]
+    pub fn to_request_body(&self, default_allowed_mentions: &AllowedMentions) -> Result<String, Error> {
+        self.validate()?;
+        #[derive(Serialize, Debug)]
+        struct WebhookExecuteBody<'a> {
+            content: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            username: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            avatar_url: Option<&'a str>,
+            embeds: Vec<&'a EmbedBuilder>,
+            allowed_mentions: AllowedMentions,
+        }
+        serde_json::to_string(&WebhookExecuteBody {
+            content: &self.message.content,
+            username: self.username.as_deref(),
+            avatar_url: self.avatar_url.as_deref(),
+            allowed_mentions: self.message.resolve_allowed_mentions(default_allowed_mentions),
+            embeds: self
+                .message
+                .embed
+                .iter()
+                .chain(self.embeds.iter())
+                .collect(),
+        }).map_err(|e| {
+            Error::Other(format!(
+                "Failed to serialize webhook message body: {:?}",
+                e
+            ))
+        })
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug)]
+/// Builder for a message embed. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#embed-object))
+pub struct EmbedBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<Timestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     color: Option<u32>,
-    footer: Option<&'a EmbedFooter<'a>>,
-    image: Option<&'a str>,
-    thumbnail: Option<&'a str>,
-    author: Option<&'a EmbedAuthor<'a>>,
-    fields: Vec<&'a EmbedField<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    footer: Option<EmbedFooter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<EmbedImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail: Option<EmbedImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    video: Option<EmbedVideo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<EmbedProvider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<EmbedAuthor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<EmbedField>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
 }
 
 #[allow(missing_docs)]
-impl<'a> EmbedBuilder<'a> {
+impl EmbedBuilder {
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn set_title(&mut self, title: &'a str) {
-        self.title = Some(title);
+    pub fn set_title<S: Into<String>>(&mut self, title: S) {
+        self.title = Some(title.into());
     }
 
-    pub fn with_title(mut self, title: &'a str) -> Self {
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
         self.set_title(title);
         self
     }
 
-    pub fn set_author(&mut self, author: &'a EmbedAuthor<'a>) {
+    /// Set the URL the title links to
+    pub fn set_url<S: Into<String>>(&mut self, url: S) {
+        self.url = Some(url.into());
+    }
+
+    /// Set the URL the title links to
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.set_url(url);
+        self
+    }
+
+    pub fn set_author(&mut self, author: EmbedAuthor) {
         self.author = Some(author);
     }
 
-    pub fn with_author(mut self, author: &'a EmbedAuthor<'a>) -> Self {
+    pub fn with_author(mut self, author: EmbedAuthor) -> Self {
         self.set_author(author);
         self
     }
@@ -97,103 +666,326 @@ impl<'a> EmbedBuilder<'a> {
         self
     }
 
-    pub fn add_field(&mut self, field: &'a EmbedField<'a>) {
+    pub fn add_field(&mut self, field: EmbedField) {
         self.fields.push(field);
     }
 
-    pub fn with_field(mut self, field: &'a EmbedField<'a>) -> Self {
+    pub fn with_field(mut self, field: EmbedField) -> Self {
         self.add_field(field);
         self
     }
 
-    pub fn set_footer(&mut self, footer: &'a EmbedFooter<'a>) {
+    pub fn set_footer(&mut self, footer: EmbedFooter) {
         self.footer = Some(footer);
     }
 
-    pub fn with_footer(mut self, footer: &'a EmbedFooter<'a>) -> Self {
+    pub fn with_footer(mut self, footer: EmbedFooter) -> Self {
         self.set_footer(footer);
         self
     }
 
-    pub fn set_description(&mut self, description: &'a str) {
-        self.description = Some(description);
+    pub fn set_description<S: Into<String>>(&mut self, description: S) {
+        self.description = Some(description.into());
     }
 
-    pub fn with_description(mut self, description: &'a str) -> Self {
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
         self.set_description(description);
         self
     }
 
-    pub fn set_timestamp(&mut self, timestamp: &'a str) {
-        self.timestamp = Some(timestamp);
+    /// Set the time shown in the footer, from a [`Timestamp`](../events/type.Timestamp.html) or `SystemTime`
+    pub fn set_timestamp<T: Into<Timestamp>>(&mut self, timestamp: T) {
+        self.timestamp = Some(timestamp.into());
     }
 
-    pub fn with_timestamp(mut self, timestamp: &'a str) -> Self {
+    /// Set the time shown in the footer, from a [`Timestamp`](../events/type.Timestamp.html) or `SystemTime`
+    pub fn with_timestamp<T: Into<Timestamp>>(mut self, timestamp: T) -> Self {
         self.set_timestamp(timestamp);
         self
     }
+
+    /// Check this embed against Discord's documented length limits
+    pub fn validate(&self) -> Result<(), Error> {
+        let total = self.validate_at("embed")?;
+        check_limit("embed", MAX_EMBED_TOTAL_LENGTH, total)
+    }
+
+    /// Checks each field's limits, returning the number of characters that count towards the total
+    fn validate_at(&self, path: &str) -> Result<usize, Error> {
+        let mut total = 0;
+        if let Some(ref title) = self.title {
+            total += check_length(&format!("{}.title", path), MAX_EMBED_TITLE_LENGTH, title)?;
+        }
+        if let Some(ref description) = self.description {
+            total += check_length(
+                &format!("{}.description", path),
+                MAX_EMBED_DESCRIPTION_LENGTH,
+                description,
+            )?;
+        }
+        check_limit(&format!("{}.fields", path), MAX_EMBED_FIELDS, self.fields.len())?;
+        for (i, field) in self.fields.iter().enumerate() {
+            total += check_length(
+                &format!("{}.fields[{}].name", path, i),
+                MAX_EMBED_FIELD_NAME_LENGTH,
+                &field.name,
+            )?;
+            total += check_length(
+                &format!("{}.fields[{}].value", path, i),
+                MAX_EMBED_FIELD_VALUE_LENGTH,
+                &field.value,
+            )?;
+        }
+        if let Some(ref footer) = self.footer {
+            total += check_length(
+                &format!("{}.footer.text", path),
+                MAX_EMBED_FOOTER_LENGTH,
+                &footer.text,
+            )?;
+        }
+        if let Some(EmbedAuthor { name: Some(ref name), .. }) = self.author {
+            total += check_length(
+                &format!("{}.author.name", path),
+                MAX_EMBED_AUTHOR_NAME_LENGTH,
+                name,
+            )?;
+        }
+        Ok(total)
+    }
+
+    /// Set the large image shown below the description
+    pub fn set_image(&mut self, image: EmbedImage) {
+        self.image = Some(image);
+    }
+
+    /// Set the large image shown below the description
+    pub fn with_image(mut self, image: EmbedImage) -> Self {
+        self.set_image(image);
+        self
+    }
+
+    /// Set the small image shown next to the title
+    pub fn set_thumbnail(&mut self, thumbnail: EmbedImage) {
+        self.thumbnail = Some(thumbnail);
+    }
+
+    /// Set the small image shown next to the title
+    pub fn with_thumbnail(mut self, thumbnail: EmbedImage) -> Self {
+        self.set_thumbnail(thumbnail);
+        self
+    }
+
+    pub fn set_video(&mut self, video: EmbedVideo) {
+        self.video = Some(video);
+    }
+
+    pub fn with_video(mut self, video: EmbedVideo) -> Self {
+        self.set_video(video);
+        self
+    }
+
+    pub fn set_provider(&mut self, provider: EmbedProvider) {
+        self.provider = Some(provider);
+    }
+
+    pub fn with_provider(mut self, provider: EmbedProvider) -> Self {
+        self.set_provider(provider);
+        self
+    }
+}
+
+/// Accessors, mainly for embeds on received messages
+impl EmbedBuilder {
+    /// Title of the embed
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Description of the embed
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// URL the title links to
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Time shown in the footer
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
+
+    /// Color of the embed's left border
+    pub fn color(&self) -> Option<u32> {
+        self.color
+    }
+
+    /// Footer of the embed
+    pub fn footer(&self) -> Option<&EmbedFooter> {
+        self.footer.as_ref()
+    }
+
+    /// Large image shown below the description
+    pub fn image(&self) -> Option<&EmbedImage> {
+        self.image.as_ref()
+    }
+
+    /// Small image shown next to the title
+    pub fn thumbnail(&self) -> Option<&EmbedImage> {
+        self.thumbnail.as_ref()
+    }
+
+    /// Video shown in the embed
+    pub fn video(&self) -> Option<&EmbedVideo> {
+        self.video.as_ref()
+    }
+
+    /// Site the embed was generated from
+    pub fn provider(&self) -> Option<&EmbedProvider> {
+        self.provider.as_ref()
+    }
+
+    /// Author of the embed
+    pub fn author(&self) -> Option<&EmbedAuthor> {
+        self.author.as_ref()
+    }
+
+    /// Fields of the embed
+    pub fn fields(&self) -> &[EmbedField] {
+        &self.fields
+    }
+
+    /// Kind of embed as set by Discord, such as `rich`, `image` or `link`
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
 }
 
-#[derive(Default, Serialize, Debug)]
-/// Representation of an embed author
-pub struct EmbedAuthor<'a> {
+#[derive(Clone, Default, Deserialize, Serialize, Debug)]
+/// Representation of an [embed author](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-author-structure)
+pub struct EmbedAuthor {
     /// Name of author
-    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// URL of author
-    pub url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
     /// URL of author icon
-    pub icon_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// Proxied URL of author icon, set by Discord
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_icon_url: Option<String>,
 }
 
-impl<'a> EmbedAuthor<'a> {
+#[allow(missing_docs)]
+impl EmbedAuthor {
     /// Create an empty embed author
     pub fn new() -> Self {
         Default::default()
     }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.name = Some(name.into());
+    }
+
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub fn set_url<S: Into<String>>(&mut self, url: S) {
+        self.url = Some(url.into());
+    }
+
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.set_url(url);
+        self
+    }
+
+    pub fn set_icon_url<S: Into<String>>(&mut self, icon_url: S) {
+        self.icon_url = Some(icon_url.into());
+    }
+
+    pub fn with_icon_url<S: Into<String>>(mut self, icon_url: S) -> Self {
+        self.set_icon_url(icon_url);
+        self
+    }
 }
 
-#[derive(Serialize, Debug)]
-/// Representation of an embed footer
-pub struct EmbedFooter<'a> {
-    text: &'a str,
-    icon_url: Option<&'a str>,
+#[derive(Clone, Deserialize, Serialize, Debug)]
+/// Representation of an [embed footer](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-footer-structure)
+pub struct EmbedFooter {
+    /// Footer text
+    pub text: String,
+    /// URL of footer icon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// Proxied URL of footer icon, set by Discord
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_icon_url: Option<String>,
 }
 
-impl<'a> EmbedFooter<'a> {
+impl EmbedFooter {
     /// Create a text-only footer
-    pub fn new(text: &'a str) -> Self {
+    pub fn new<S: Into<String>>(text: S) -> Self {
         EmbedFooter {
-            text,
+            text: text.into(),
             icon_url: None,
+            proxy_icon_url: None,
         }
     }
     /// Create a footer with an icon
-    pub fn new_with_icon(text: &'a str, icon_url: &'a str) -> Self {
-        EmbedFooter {
-            text,
-            icon_url: Some(icon_url),
-        }
+    pub fn new_with_icon<S: Into<String>, T: Into<String>>(text: S, icon_url: T) -> Self {
+        EmbedFooter::new(text).with_icon_url(icon_url)
+    }
+
+    /// Set the footer text
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.text = text.into();
+    }
+
+    /// Set the footer text
+    pub fn with_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the URL of the footer icon
+    pub fn set_icon_url<S: Into<String>>(&mut self, icon_url: S) {
+        self.icon_url = Some(icon_url.into());
+    }
+
+    /// Set the URL of the footer icon
+    pub fn with_icon_url<S: Into<String>>(mut self, icon_url: S) -> Self {
+        self.set_icon_url(icon_url);
+        self
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// Representation of an [embed field](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-field-structure)
-pub struct EmbedField<'a> {
-    name: &'a str,
-    value: &'a str,
-    inline: bool,
+pub struct EmbedField {
+    /// Field name
+    pub name: String,
+    /// Field value
+    pub value: String,
+    /// Whether the field is displayed next to other inline fields
+    #[serde(default)]
+    pub inline: bool,
 }
 
-impl<'a> EmbedField<'a> {
+impl EmbedField {
     /// Create a new embed field
-    pub fn new(name: &'a str, value: &'a str) -> Self {
-        EmbedField::new_internal(name, value, false)
+    pub fn new<S: Into<String>, T: Into<String>>(name: S, value: T) -> Self {
+        EmbedField::new_internal(name.into(), value.into(), false)
     }
     /// Create a new inline embed field
-    pub fn new_inline(name: &'a str, value: &'a str) -> Self {
-        EmbedField::new_internal(name, value, true)
+    pub fn new_inline<S: Into<String>, T: Into<String>>(name: S, value: T) -> Self {
+        EmbedField::new_internal(name.into(), value.into(), true)
     }
-    fn new_internal(name: &'a str, value: &'a str, inline: bool) -> Self {
+    fn new_internal(name: String, value: String, inline: bool) -> Self {
         EmbedField {
             name,
             value,
@@ -201,3 +993,839 @@ impl<'a> EmbedField<'a> {
         }
     }
 }
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug)]
+/// Representation of an [embed image](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-image-structure) or thumbnail
+pub struct EmbedImage {
+    /// Source URL of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Proxied URL of the image, set by Discord
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    /// Height of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    /// Width of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u64>,
+}
+
+impl EmbedImage {
+    /// Create an image from its URL
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        EmbedImage {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the dimensions of the image
+    pub fn set_size(&mut self, width: u64, height: u64) {
+        self.width = Some(width);
+        self.height = Some(height);
+    }
+
+    /// Set the dimensions of the image
+    pub fn with_size(mut self, width: u64, height: u64) -> Self {
+        self.set_size(width, height);
+        self
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug)]
+/// Representation of an [embed video](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-video-structure)
+pub struct EmbedVideo {
+    /// Source URL of the video
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Height of the video
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    /// Width of the video
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u64>,
+}
+
+impl EmbedVideo {
+    /// Create a video from its URL
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        EmbedVideo {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the dimensions of the video
+    pub fn set_size(&mut self, width: u64, height: u64) {
+        self.width = Some(width);
+        self.height = Some(height);
+    }
+
+    /// Set the dimensions of the video
+    pub fn with_size(mut self, width: u64, height: u64) -> Self {
+        self.set_size(width, height);
+        self
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug)]
+/// Representation of an [embed provider](https://discordapp.com/developers/docs/resources/channel#embed-object-embed-provider-structure)
+pub struct EmbedProvider {
+    /// Name of the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// URL of the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[allow(missing_docs)]
+impl EmbedProvider {
+    /// Create an empty embed provider
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.name = Some(name.into());
+    }
+
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub fn set_url<S: Into<String>>(&mut self, url: S) {
+        self.url = Some(url.into());
+    }
+
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.set_url(url);
+        self
+    }
+}
+
+#[derive(Clone, Default, Serialize, Debug)]
+/// Builder for creating or modifying a channel
+pub struct ChannelBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<ChannelId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsfw: Option<bool>,
+}
+
+#[allow(missing_docs)]
+impl ChannelBuilder {
+    /// Create an empty ChannelBuilder. A name is required when creating a channel.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.name = Some(name.into());
+    }
+
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub fn set_kind(&mut self, kind: ChannelType) {
+        self.kind = Some(kind);
+    }
+
+    pub fn with_kind(mut self, kind: ChannelType) -> Self {
+        self.set_kind(kind);
+        self
+    }
+
+    pub fn set_topic<S: Into<String>>(&mut self, topic: S) {
+        self.topic = Some(topic.into());
+    }
+
+    pub fn with_topic<S: Into<String>>(mut self, topic: S) -> Self {
+        self.set_topic(topic);
+        self
+    }
+
+    pub fn set_bitrate(&mut self, bitrate: u64) {
+        self.bitrate = Some(bitrate);
+    }
+
+    pub fn with_bitrate(mut self, bitrate: u64) -> Self {
+        self.set_bitrate(bitrate);
+        self
+    }
+
+    pub fn set_user_limit(&mut self, user_limit: u64) {
+        self.user_limit = Some(user_limit);
+    }
+
+    pub fn with_user_limit(mut self, user_limit: u64) -> Self {
+        self.set_user_limit(user_limit);
+        self
+    }
+
+    pub fn set_rate_limit_per_user(&mut self, seconds: u64) {
+        self.rate_limit_per_user = Some(seconds);
+    }
+
+    pub fn with_rate_limit_per_user(mut self, seconds: u64) -> Self {
+        self.set_rate_limit_per_user(seconds);
+        self
+    }
+
+    pub fn set_position(&mut self, position: i64) {
+        self.position = Some(position);
+    }
+
+    pub fn with_position(mut self, position: i64) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    pub fn set_permission_overwrites(&mut self, overwrites: Vec<PermissionOverwrite>) {
+        self.permission_overwrites = Some(overwrites);
+    }
+
+    pub fn with_permission_overwrites(mut self, overwrites: Vec<PermissionOverwrite>) -> Self {
+        self.set_permission_overwrites(overwrites);
+        self
+    }
+
+    pub fn set_parent(&mut self, parent_id: ChannelId) {
+        self.parent_id = Some(parent_id);
+    }
+
+    pub fn with_parent(mut self, parent_id: ChannelId) -> Self {
+        self.set_parent(parent_id);
+        self
+    }
+
+    pub fn set_nsfw(&mut self, nsfw: bool) {
+        self.nsfw = Some(nsfw);
+    }
+
+    pub fn with_nsfw(mut self, nsfw: bool) -> Self {
+        self.set_nsfw(nsfw);
+        self
+    }
+}
+
+#[derive(Clone, Default, Serialize, Debug)]
+/// Builder for creating a channel invite
+pub struct InviteBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_age: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_uses: Option<u64>,
+    temporary: bool,
+    unique: bool,
+}
+
+impl InviteBuilder {
+    /// Create an InviteBuilder with Discord's defaults
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the duration in seconds after which the invite expires, or 0 for never
+    pub fn set_max_age(&mut self, seconds: u64) {
+        self.max_age = Some(seconds);
+    }
+
+    /// Set the duration in seconds after which the invite expires, or 0 for never
+    pub fn with_max_age(mut self, seconds: u64) -> Self {
+        self.set_max_age(seconds);
+        self
+    }
+
+    /// Set the maximum number of uses, or 0 for unlimited
+    pub fn set_max_uses(&mut self, uses: u64) {
+        self.max_uses = Some(uses);
+    }
+
+    /// Set the maximum number of uses, or 0 for unlimited
+    pub fn with_max_uses(mut self, uses: u64) -> Self {
+        self.set_max_uses(uses);
+        self
+    }
+
+    /// Set whether the invite only grants temporary membership
+    pub fn set_temporary(&mut self, temporary: bool) {
+        self.temporary = temporary;
+    }
+
+    /// Set whether the invite only grants temporary membership
+    pub fn with_temporary(mut self, temporary: bool) -> Self {
+        self.set_temporary(temporary);
+        self
+    }
+
+    /// Set whether to always create a new invite instead of reusing a similar one
+    pub fn set_unique(&mut self, unique: bool) {
+        self.unique = unique;
+    }
+
+    /// Set whether to always create a new invite instead of reusing a similar one
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        self.set_unique(unique);
+        self
+    }
+}
+
+#[derive(Clone, Default, Serialize, Debug)]
+/// Builder for modifying a guild member
+pub struct MemberBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<RoleId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deaf: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<ChannelId>,
+}
+
+#[allow(missing_docs)]
+impl MemberBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the member's nickname. An empty string removes it.
+    pub fn set_nick<S: Into<String>>(&mut self, nick: S) {
+        self.nick = Some(nick.into());
+    }
+
+    pub fn with_nick<S: Into<String>>(mut self, nick: S) -> Self {
+        self.set_nick(nick);
+        self
+    }
+
+    /// Replace the member's roles
+    pub fn set_roles(&mut self, roles: Vec<RoleId>) {
+        self.roles = Some(roles);
+    }
+
+    pub fn with_roles(mut self, roles: Vec<RoleId>) -> Self {
+        self.set_roles(roles);
+        self
+    }
+
+    pub fn set_mute(&mut self, mute: bool) {
+        self.mute = Some(mute);
+    }
+
+    pub fn with_mute(mut self, mute: bool) -> Self {
+        self.set_mute(mute);
+        self
+    }
+
+    pub fn set_deaf(&mut self, deaf: bool) {
+        self.deaf = Some(deaf);
+    }
+
+    pub fn with_deaf(mut self, deaf: bool) -> Self {
+        self.set_deaf(deaf);
+        self
+    }
+
+    /// Move the member to another voice channel
+    pub fn set_channel(&mut self, channel_id: ChannelId) {
+        self.channel_id = Some(channel_id);
+    }
+
+    pub fn with_channel(mut self, channel_id: ChannelId) -> Self {
+        self.set_channel(channel_id);
+        self
+    }
+}
+
+#[derive(Clone, Default, Serialize, Debug)]
+/// Builder for creating or modifying a role
+pub struct RoleBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+#[allow(missing_docs)]
+impl RoleBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.name = Some(name.into());
+    }
+
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub fn set_permissions(&mut self, permissions: u64) {
+        self.permissions = Some(permissions);
+    }
+
+    pub fn with_permissions(mut self, permissions: u64) -> Self {
+        self.set_permissions(permissions);
+        self
+    }
+
+    pub fn set_color(&mut self, color: u32) {
+        self.color = Some(color);
+    }
+
+    pub fn with_color(mut self, color: u32) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_hoist(&mut self, hoist: bool) {
+        self.hoist = Some(hoist);
+    }
+
+    pub fn with_hoist(mut self, hoist: bool) -> Self {
+        self.set_hoist(hoist);
+        self
+    }
+
+    pub fn set_mentionable(&mut self, mentionable: bool) {
+        self.mentionable = Some(mentionable);
+    }
+
+    pub fn with_mentionable(mut self, mentionable: bool) -> Self {
+        self.set_mentionable(mentionable);
+        self
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+/// Filters for fetching a guild's audit log
+pub struct AuditLogFilter {
+    user_id: Option<UserId>,
+    action_type: Option<AuditLogEvent>,
+    before: Option<Snowflake>,
+    limit: Option<u8>,
+}
+
+impl AuditLogFilter {
+    /// Create an empty filter, matching the most recent entries
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Only include actions taken by this user
+    pub fn set_user(&mut self, user_id: UserId) {
+        self.user_id = Some(user_id);
+    }
+
+    /// Only include actions taken by this user
+    pub fn with_user(mut self, user_id: UserId) -> Self {
+        self.set_user(user_id);
+        self
+    }
+
+    /// Only include actions of this type
+    pub fn set_action_type(&mut self, action_type: AuditLogEvent) {
+        self.action_type = Some(action_type);
+    }
+
+    /// Only include actions of this type
+    pub fn with_action_type(mut self, action_type: AuditLogEvent) -> Self {
+        self.set_action_type(action_type);
+        self
+    }
+
+    /// Only include entries older than this entry ID
+    pub fn set_before(&mut self, entry_id: Snowflake) {
+        self.before = Some(entry_id);
+    }
+
+    /// Only include entries older than this entry ID
+    pub fn with_before(mut self, entry_id: Snowflake) -> Self {
+        self.set_before(entry_id);
+        self
+    }
+
+    /// Set the maximum number of entries to return, from 1 to 100
+    pub fn set_limit(&mut self, limit: u8) {
+        self.limit = Some(limit);
+    }
+
+    /// Set the maximum number of entries to return, from 1 to 100
+    pub fn with_limit(mut self, limit: u8) -> Self {
+        self.set_limit(limit);
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn to_query_string(&self) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(user_id) = self.user_id {
+            query.append_pair("user_id", &user_id.to_string());
+        }
+        if let Some(action_type) = self.action_type {
+            query.append_pair("action_type", &u64::from(action_type).to_string());
+        }
+        if let Some(before) = self.before {
+            query.append_pair("before", &before.to_string());
+        }
+        if let Some(limit) = self.limit {
+            query.append_pair("limit", &limit.to_string());
+        }
+        query.finish()
+    }
+}
+
+#[derive(Clone, Default, Serialize, Debug)]
+/// Builder for creating or modifying a webhook
+pub struct WebhookBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<ChannelId>,
+}
+
+impl WebhookBuilder {
+    /// Create an empty WebhookBuilder. A name is required when creating a webhook.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the default name of the webhook
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.name = Some(name.into());
+    }
+
+    /// Set the default name of the webhook
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Set the default avatar, as [image data](https://discordapp.com/developers/docs/reference#image-data) like `data:image/png;base64,...`
+    pub fn set_avatar<S: Into<String>>(&mut self, avatar: S) {
+        self.avatar = Some(avatar.into());
+    }
+
+    /// Set the default avatar, as [image data](https://discordapp.com/developers/docs/reference#image-data) like `data:image/png;base64,...`
+    pub fn with_avatar<S: Into<String>>(mut self, avatar: S) -> Self {
+        self.set_avatar(avatar);
+        self
+    }
+
+    /// Move the webhook to another channel. Only used when modifying a webhook.
+    pub fn set_channel(&mut self, channel_id: ChannelId) {
+        self.channel_id = Some(channel_id);
+    }
+
+    /// Move the webhook to another channel. Only used when modifying a webhook.
+    pub fn with_channel(mut self, channel_id: ChannelId) -> Self {
+        self.set_channel(channel_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use events::{MessageId, RoleId, Snowflake, UserId};
+
+    fn fence_count(chunk: &str) -> usize {
+        chunk.matches("```").count()
+    }
+
+    #[test]
+    fn split_content_keeps_short_content_whole() {
+        assert_eq!(split_content("hello\nworld", 2000), vec!["hello\nworld"]);
+    }
+
+    #[test]
+    fn split_content_prefers_line_breaks() {
+        let content = format!("{}\n{}", "a".repeat(15), "b".repeat(15));
+        assert_eq!(
+            split_content(&content, 20),
+            vec!["a".repeat(15), "b".repeat(15)]
+        );
+    }
+
+    #[test]
+    fn split_content_respects_limit() {
+        let content = format!(
+            "intro\n```rust\n{}\n```\n{}",
+            "let x = 1;\n".repeat(300),
+            "word ".repeat(800)
+        );
+        let chunks = split_content(&content, 2000);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 2000, "{} characters", chunk.len());
+            assert_eq!(fence_count(chunk) % 2, 0, "unbalanced chunk: {:?}", chunk);
+        }
+    }
+
+    #[test]
+    fn split_content_reopens_code_block_with_language() {
+        let content = format!("```py\n{}```", "print(1)\n".repeat(500));
+        let chunks = split_content(&content, 2000);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("```py\n"), "{:?}", &chunk[..10]);
+            assert!(chunk.ends_with("\n```"));
+        }
+    }
+
+    #[test]
+    fn split_content_skips_empty_code_block() {
+        let content = format!("```\n{}\n```", "x".repeat(4500));
+        let chunks = split_content(&content, 2000);
+        assert_eq!(chunks.len(), 3);
+        for chunk in &chunks {
+            assert!(chunk.contains('x'), "chunk without content: {:?}", chunk);
+            assert!(chunk.chars().count() <= 2000);
+            assert_eq!(fence_count(chunk), 2);
+        }
+    }
+
+    #[test]
+    fn split_content_balances_fence_opened_by_long_line() {
+        let content = format!("```py {}\nfoo\n```", "y ".repeat(1500));
+        let chunks = split_content(&content, 2000);
+        assert_eq!(chunks.len(), 2);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 2000);
+            assert_eq!(fence_count(chunk), 2, "unbalanced chunk: {:?}", chunk);
+        }
+        assert!(chunks[1].starts_with("```py\n"));
+        assert!(chunks[1].ends_with("foo\n```"));
+    }
+
+    #[test]
+    fn split_content_keeps_backtick_runs_together() {
+        let content = format!("{}```py\nprint(1)\n```", "a".repeat(1995));
+        let chunks = split_content(&content, 2000);
+        assert_eq!(chunks, vec!["a".repeat(1995), "```py\nprint(1)\n```".to_owned()]);
+    }
+
+    #[test]
+    fn split_content_carries_empty_block_fence_within_limit() {
+        let content = format!("{}```\n\nfoo\n```", "a".repeat(1993));
+        let chunks = split_content(&content, 2000);
+        assert_eq!(chunks, vec!["a".repeat(1993), "```\n\nfoo\n```".to_owned()]);
+    }
+
+    #[test]
+    fn split_content_respects_limit_with_mixed_input() {
+        // Small linear congruential generator, so the input is varied but reproducible
+        let mut state: u32 = 12345;
+        let mut next = move |bound: usize| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as usize % bound
+        };
+        for limit in &[40, 64, 100, 2000] {
+            let mut lines = Vec::new();
+            for _ in 0..300 {
+                let width = next(limit * 3 / 2) + 1;
+                // Fences that end just before or just past the limit are the interesting cases
+                let near_limit = limit - 8 + next(6);
+                lines.push(match next(8) {
+                    0 => "```py".to_owned(),
+                    1 => "```".to_owned(),
+                    2 => format!("{}```", "b".repeat(near_limit)),
+                    3 => format!("{}```py", "d".repeat(near_limit)),
+                    4 => "word ".repeat(width / 5 + 1),
+                    5 => String::new(),
+                    _ => "c".repeat(width),
+                });
+            }
+            let content = lines.join("\n");
+            for chunk in split_content(&content, *limit) {
+                assert!(
+                    chunk.chars().count() <= *limit,
+                    "{} characters with a limit of {}: {:?}",
+                    chunk.chars().count(),
+                    limit,
+                    chunk
+                );
+                assert!(!chunk.trim().is_empty());
+            }
+        }
+    }
+
+    fn allowed_mentions_json(allowed_mentions: &AllowedMentions) -> serde_json::Value {
+        serde_json::to_value(allowed_mentions).unwrap()
+    }
+
+    fn request_allowed_mentions(
+        message: &MessageBuilder,
+        default: &AllowedMentions,
+    ) -> serde_json::Value {
+        let body: serde_json::Value =
+            serde_json::from_str(&message.to_request_body(default).unwrap()).unwrap();
+        body["allowed_mentions"].clone()
+    }
+
+    #[test]
+    fn allowed_mentions_parse_types() {
+        assert_eq!(
+            allowed_mentions_json(&AllowedMentions::all()),
+            json!({"parse": ["users", "roles", "everyone"]})
+        );
+        assert_eq!(
+            allowed_mentions_json(&AllowedMentions::default()),
+            json!({"parse": ["users", "roles"]})
+        );
+        assert_eq!(
+            allowed_mentions_json(&AllowedMentions::none()),
+            json!({"parse": []})
+        );
+    }
+
+    #[test]
+    fn allowed_mentions_explicit_users_replace_parse() {
+        let allowed_mentions = AllowedMentions::all().with_user(UserId(Snowflake(1)));
+        assert_eq!(
+            allowed_mentions_json(&allowed_mentions),
+            json!({"parse": ["roles", "everyone"], "users": ["1"]})
+        );
+    }
+
+    #[test]
+    fn allowed_mentions_explicit_roles_replace_parse() {
+        let allowed_mentions = AllowedMentions::default().with_role(RoleId(Snowflake(2)));
+        assert_eq!(
+            allowed_mentions_json(&allowed_mentions),
+            json!({"parse": ["users"], "roles": ["2"]})
+        );
+        let allowed_mentions = AllowedMentions::none()
+            .with_role(RoleId(Snowflake(2)))
+            .with_replied_user(true);
+        assert_eq!(
+            allowed_mentions_json(&allowed_mentions),
+            json!({"parse": [], "roles": ["2"], "replied_user": true})
+        );
+    }
+
+    #[test]
+    fn message_falls_back_to_default_allowed_mentions() {
+        let message = MessageBuilder::new("hi");
+        assert_eq!(
+            request_allowed_mentions(&message, &AllowedMentions::none()),
+            json!({"parse": []})
+        );
+        let message = message.with_allowed_mentions(AllowedMentions::all());
+        assert_eq!(
+            request_allowed_mentions(&message, &AllowedMentions::none()),
+            json!({"parse": ["users", "roles", "everyone"]})
+        );
+    }
+
+    #[test]
+    fn message_merges_replied_user_into_allowed_mentions() {
+        let message = MessageBuilder::new("hi")
+            .with_reply(MessageId(Snowflake(3)), false)
+            .with_mention_replied_user(false);
+        assert_eq!(
+            request_allowed_mentions(&message, &AllowedMentions::default()),
+            json!({"parse": ["users", "roles"], "replied_user": false})
+        );
+        let message = message.with_allowed_mentions(AllowedMentions::none());
+        assert_eq!(
+            request_allowed_mentions(&message, &AllowedMentions::default()),
+            json!({"parse": [], "replied_user": false})
+        );
+    }
+
+    fn limit_error(result: Result<(), Error>) -> LimitError {
+        match result {
+            Err(Error::LimitExceeded(err)) => err,
+            other => panic!("expected a limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_counts_content_in_chars() {
+        assert!(MessageBuilder::new("é".repeat(2000)).validate().is_ok());
+        let err = limit_error(MessageBuilder::new("é".repeat(2001)).validate());
+        assert_eq!((err.field.as_str(), err.limit, err.actual), ("content", 2000, 2001));
+    }
+
+    #[test]
+    fn validate_reports_embed_field_path() {
+        let mut embed = EmbedBuilder::new();
+        for i in 0..3 {
+            embed.add_field(EmbedField::new(format!("name {}", i), "value"));
+        }
+        embed.add_field(EmbedField::new("name 3", "v".repeat(1025)));
+        let err = limit_error(MessageBuilder::new("hi").with_embed(embed).validate());
+        assert_eq!(
+            (err.field.as_str(), err.limit, err.actual),
+            ("embed.fields[3].value", 1024, 1025)
+        );
+    }
+
+    #[test]
+    fn validate_caps_embed_fields() {
+        let mut embed = EmbedBuilder::new();
+        for i in 0..25 {
+            embed.add_field(EmbedField::new(format!("name {}", i), "value"));
+        }
+        assert!(embed.validate().is_ok());
+        embed.add_field(EmbedField::new("name 25", "value"));
+        let err = limit_error(embed.validate());
+        assert_eq!((err.field.as_str(), err.limit, err.actual), ("embed.fields", 25, 26));
+    }
+
+    #[test]
+    fn validate_reports_webhook_embed_path() {
+        let message = WebhookMessageBuilder::new(MessageBuilder::new("hi"))
+            .with_embed(EmbedBuilder::new().with_title("fine"))
+            .with_embed(EmbedBuilder::new().with_title("t".repeat(257)));
+        let err = limit_error(message.validate());
+        assert_eq!(
+            (err.field.as_str(), err.limit, err.actual),
+            ("embeds[1].title", 256, 257)
+        );
+    }
+
+    #[test]
+    fn validate_totals_all_webhook_embeds() {
+        let embed = EmbedBuilder::new().with_description("d".repeat(3500));
+        assert!(embed.validate().is_ok());
+        let message = MessageBuilder::new("hi").with_embed(embed.clone());
+        let message = WebhookMessageBuilder::new(message).with_embed(embed);
+        let err = limit_error(message.validate());
+        assert_eq!((err.field.as_str(), err.limit, err.actual), ("embeds", 6000, 7000));
+    }
+}
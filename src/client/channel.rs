@@ -0,0 +1,170 @@
+use hyper;
+
+use builder::{ChannelBuilder, InviteBuilder};
+use events::{Channel, ChannelId, GuildId, Invite, PermissionOverwrite, Snowflake};
+use Error;
+
+use futures::Future;
+
+use super::{to_body, Client};
+
+impl Client {
+    /// Fetch a channel by ID
+    pub fn get_channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = Channel, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/channels/{}", channel_id),
+            None,
+            None,
+        )
+    }
+
+    /// List the channels in a guild
+    pub fn get_guild_channels(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Future<Item = Vec<Channel>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/channels", guild_id),
+            None,
+            None,
+        )
+    }
+
+    /// Create a channel in a guild
+    pub fn create_channel(
+        &self,
+        guild_id: GuildId,
+        channel: &ChannelBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Channel, Error = Error> + Send {
+        let body = try_future!(to_body(channel));
+        self.request_json(
+            hyper::Method::POST,
+            &format!("/guilds/{}/channels", guild_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Modify a channel's settings
+    pub fn edit_channel(
+        &self,
+        channel_id: ChannelId,
+        channel: &ChannelBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Channel, Error = Error> + Send {
+        let body = try_future!(to_body(channel));
+        self.request_json(
+            hyper::Method::PATCH,
+            &format!("/channels/{}", channel_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Delete a guild channel, or close a DM
+    pub fn delete_channel(
+        &self,
+        channel_id: ChannelId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Channel, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::DELETE,
+            &format!("/channels/{}", channel_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Create or replace a permission overwrite on a channel
+    pub fn edit_channel_permissions(
+        &self,
+        channel_id: ChannelId,
+        overwrite: &PermissionOverwrite,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        let body = try_future!(to_body(overwrite));
+        self.request_empty(
+            hyper::Method::PUT,
+            &format!("/channels/{}/permissions/{}", channel_id, overwrite.id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Remove the permission overwrite for a role or user from a channel
+    pub fn delete_channel_permission(
+        &self,
+        channel_id: ChannelId,
+        overwrite_id: Snowflake,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/channels/{}/permissions/{}", channel_id, overwrite_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Show the typing indicator in a channel for a few seconds
+    pub fn trigger_typing(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::POST,
+            &format!("/channels/{}/typing", channel_id),
+            None,
+            None,
+        )
+    }
+
+    /// List the invites for a channel
+    pub fn get_channel_invites(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = Vec<Invite>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/channels/{}/invites", channel_id),
+            None,
+            None,
+        )
+    }
+
+    /// Create an invite for a channel
+    pub fn create_invite(
+        &self,
+        channel_id: ChannelId,
+        invite: &InviteBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Invite, Error = Error> + Send {
+        let body = try_future!(to_body(invite));
+        self.request_json(
+            hyper::Method::POST,
+            &format!("/channels/{}/invites", channel_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Delete an invite by its code
+    pub fn delete_invite(
+        &self,
+        code: &str,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Invite, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::DELETE,
+            &format!("/invites/{}", code),
+            None,
+            reason,
+        )
+    }
+}
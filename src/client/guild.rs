@@ -0,0 +1,280 @@
+use futures;
+use hyper;
+
+use builder::{AuditLogFilter, MemberBuilder, RoleBuilder};
+use events::{AuditLog, Ban, Guild, GuildId, Member, Role, RoleId, UserId};
+use Error;
+
+use futures::{Future, Stream};
+
+use super::{to_body, Client};
+
+/// Maximum number of members Discord returns per page
+const MEMBERS_PAGE_SIZE: usize = 1000;
+
+impl Client {
+    /// Fetch a guild by ID
+    pub fn get_guild(&self, guild_id: GuildId) -> impl Future<Item = Guild, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}", guild_id),
+            None,
+            None,
+        )
+    }
+
+    /// List all members of a guild, fetching pages as the stream is polled
+    pub fn get_members(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Stream<Item = Member, Error = Error> + Send {
+        let client = self.clone();
+        futures::stream::unfold(Some(UserId::from(0)), move |after| {
+            after.map(|after| {
+                client
+                    .request_json::<Vec<Member>>(
+                        hyper::Method::GET,
+                        &format!(
+                            "/guilds/{}/members?limit={}&after={}",
+                            guild_id, MEMBERS_PAGE_SIZE, after
+                        ),
+                        None,
+                        None,
+                    )
+                    .map(|page| {
+                        let next = if page.len() < MEMBERS_PAGE_SIZE {
+                            None
+                        } else {
+                            page.last().map(|member| member.user.id)
+                        };
+                        (page, next)
+                    })
+            })
+        }).map(futures::stream::iter_ok)
+            .flatten()
+    }
+
+    /// Fetch a single member of a guild
+    pub fn get_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> impl Future<Item = Member, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/members/{}", guild_id, user_id),
+            None,
+            None,
+        )
+    }
+
+    /// Modify a member's nickname, roles, voice state or voice channel
+    pub fn edit_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        member: &MemberBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        let body = try_future!(to_body(member));
+        self.request_empty(
+            hyper::Method::PATCH,
+            &format!("/guilds/{}/members/{}", guild_id, user_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Give a role to a member
+    pub fn add_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::PUT,
+            &format!("/guilds/{}/members/{}/roles/{}", guild_id, user_id, role_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Take a role away from a member
+    pub fn remove_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/guilds/{}/members/{}/roles/{}", guild_id, user_id, role_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Remove a member from a guild
+    pub fn kick_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/guilds/{}/members/{}", guild_id, user_id),
+            None,
+            reason,
+        )
+    }
+
+    /// List the bans in a guild
+    pub fn get_bans(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Future<Item = Vec<Ban>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/bans", guild_id),
+            None,
+            None,
+        )
+    }
+
+    /// Ban a user from a guild, optionally deleting their messages from the last 0-7 days
+    pub fn ban_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        delete_message_days: Option<u8>,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        let path = match delete_message_days {
+            Some(days) => format!(
+                "/guilds/{}/bans/{}?delete-message-days={}",
+                guild_id, user_id, days
+            ),
+            None => format!("/guilds/{}/bans/{}", guild_id, user_id),
+        };
+        self.request_empty(hyper::Method::PUT, &path, None, reason)
+    }
+
+    /// Remove a ban from a guild
+    pub fn unban_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/guilds/{}/bans/{}", guild_id, user_id),
+            None,
+            reason,
+        )
+    }
+
+    /// List the roles in a guild
+    pub fn get_roles(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Future<Item = Vec<Role>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/roles", guild_id),
+            None,
+            None,
+        )
+    }
+
+    /// Create a role in a guild
+    pub fn create_role(
+        &self,
+        guild_id: GuildId,
+        role: &RoleBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Role, Error = Error> + Send {
+        let body = try_future!(to_body(role));
+        self.request_json(
+            hyper::Method::POST,
+            &format!("/guilds/{}/roles", guild_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Modify a role
+    pub fn edit_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        role: &RoleBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Role, Error = Error> + Send {
+        let body = try_future!(to_body(role));
+        self.request_json(
+            hyper::Method::PATCH,
+            &format!("/guilds/{}/roles/{}", guild_id, role_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Delete a role
+    pub fn delete_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/guilds/{}/roles/{}", guild_id, role_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Change the positions of roles, given as `(role_id, position)` pairs
+    pub fn reorder_roles(
+        &self,
+        guild_id: GuildId,
+        positions: &[(RoleId, i64)],
+        reason: Option<&str>,
+    ) -> impl Future<Item = Vec<Role>, Error = Error> + Send {
+        #[derive(Serialize)]
+        struct RolePosition {
+            id: RoleId,
+            position: i64,
+        }
+        let positions: Vec<_> = positions
+            .iter()
+            .map(|&(id, position)| RolePosition { id, position })
+            .collect();
+        let body = try_future!(to_body(&positions));
+        self.request_json(
+            hyper::Method::PATCH,
+            &format!("/guilds/{}/roles", guild_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Fetch entries from a guild's audit log
+    pub fn audit_log(
+        &self,
+        guild_id: GuildId,
+        filter: &AuditLogFilter,
+    ) -> impl Future<Item = AuditLog, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/audit-logs?{}", guild_id, filter.to_query_string()),
+            None,
+            None,
+        )
+    }
+}
@@ -0,0 +1,97 @@
+use hyper;
+
+use builder::MessageBuilder;
+use events::{ChannelId, MessageId, ReceivedMessage};
+use Error;
+
+use futures;
+use futures::{Future, Stream};
+
+use super::Client;
+
+impl Client {
+    /// Pin a message in its channel
+    pub fn pin_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::PUT,
+            &format!("/channels/{}/pins/{}", channel_id, message_id),
+            None,
+            reason,
+        )
+    }
+
+    /// Unpin a message from its channel
+    pub fn unpin_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/channels/{}/pins/{}", channel_id, message_id),
+            None,
+            reason,
+        )
+    }
+
+    /// List the pinned messages in a channel
+    pub fn get_pinned_messages(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = Vec<ReceivedMessage>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/channels/{}/pins", channel_id),
+            None,
+            None,
+        )
+    }
+
+    /// Publish a message in an announcement channel to the channels following it
+    pub fn crosspost_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> impl Future<Item = ReceivedMessage, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::POST,
+            &format!("/channels/{}/messages/{}/crosspost", channel_id, message_id),
+            None,
+            None,
+        )
+    }
+
+    /// Send a message that may be longer than Discord allows, split into several messages
+    ///
+    /// The parts are sent in order, see [`MessageBuilder::split`](../builder/struct.MessageBuilder.html#method.split).
+    /// Resolves to the IDs of the created messages.
+    pub fn send_long_message(
+        &self,
+        message: &MessageBuilder,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = Vec<MessageId>, Error = Error> + Send {
+        let bodies = try_future!(
+            message
+                .split()
+                .iter()
+                .map(|part| part.to_request_body(&self.default_allowed_mentions))
+                .collect::<Result<Vec<_>, _>>()
+        );
+        let client = self.clone();
+        let path = format!("/channels/{}/messages", channel_id);
+        futures::stream::iter_ok(bodies)
+            .and_then(move |body| {
+                client
+                    .request_json::<ReceivedMessage>(hyper::Method::POST, &path, Some(body), None)
+                    .map(|message| message.id)
+            })
+            .collect()
+            .into()
+    }
+}
@@ -1,21 +1,43 @@
 use futures;
 use hyper;
 use hyper_tls;
+use serde;
 use serde_json;
 use url;
 
+use builder::AllowedMentions;
+use events::{Channel, ChannelId, UserId};
 use Error;
 
 use futures::{Future, IntoFuture, Stream};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing_futures::Instrument;
 
+mod channel;
+mod guild;
+mod message;
 mod stream;
+mod webhook;
 
 pub use self::stream::GatewayConnection;
+pub use self::webhook::WebhookClient;
 
+const API_BASE: &str = "https://discordapp.com/api/v6";
+
+/// ID of the next REST request, used to tell requests apart in logs
+static NEXT_REQUEST_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
 /// Object used to interact with the Discord API
+///
+/// Methods that modify a guild take an optional `reason`, which is shown in the guild's audit log.
 pub struct Client {
-    http_client: hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>,
+    http_client: HttpsClient,
     token: String,
+    dm_channels: Arc<Mutex<HashMap<UserId, ChannelId>>>,
+    default_allowed_mentions: AllowedMentions,
 }
 
 impl Client {
@@ -27,12 +49,10 @@ impl Client {
         {
             let auth_value = format!("Bot {}", token);
             let auth_value_ref: &str = &auth_value;
-            hyper_tls::HttpsConnector::new(1)
-                .map(|connector| hyper::Client::builder().build(connector))
-                .map_err(|e| e.into())
+            new_http_client()
                 .into_future()
                 .join(
-                    hyper::Request::get("https://discordapp.com/api/v6/gateway/bot")
+                    hyper::Request::get(format!("{}/gateway/bot", API_BASE))
                     .header(hyper::header::AUTHORIZATION, auth_value_ref)
                     .body(Default::default())
                     .map_err(|e| Error::Other(format!("{:?}", e)))
@@ -42,7 +62,7 @@ impl Client {
             http.request(gateway_req)
                 .map_err(|e| e.into())
                 .and_then(
-                    |resp| -> Box<Future<Item = hyper::Chunk, Error = Error> + Send> {
+                    |resp| -> Box<dyn Future<Item = hyper::Chunk, Error = Error> + Send> {
                         match resp.status() {
                             hyper::StatusCode::UNAUTHORIZED => {
                                 Box::new(futures::future::err(Error::AuthenticationFailed))
@@ -66,14 +86,16 @@ impl Client {
                         Error::Other(format!("Unable to parse Gateway API response: {:?}", e))
                     })?;
 
-                    println!("{}", result.url);
-                    url::Url::parse(&result.url)
+                    debug!(url = result.url, "Fetched gateway URL");
+                    url::Url::parse(result.url)
                         .map_err(|e| Error::Other(format!("Unable to parse Gateway URL: {:?}", e)))
                         .map(|url| {
                             (
                                 Client {
                                     http_client: http,
                                     token: token.clone(),
+                                    dm_channels: Default::default(),
+                                    default_allowed_mentions: AllowedMentions::default(),
                                 },
                                 stream::GatewayConnection::connect_new(url, token),
                                 )
@@ -82,44 +104,237 @@ impl Client {
         })
     }
 
+    /// Set which mentions may ping in messages that don't set their own
+    /// [`AllowedMentions`](../builder/struct.AllowedMentions.html).
+    /// Defaults to allowing everything except `@everyone` and `@here`.
+    pub fn set_default_allowed_mentions(&mut self, allowed_mentions: AllowedMentions) {
+        self.default_allowed_mentions = allowed_mentions;
+    }
+
+    /// Set which mentions may ping in messages that don't set their own
+    /// [`AllowedMentions`](../builder/struct.AllowedMentions.html)
+    pub fn with_default_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.set_default_allowed_mentions(allowed_mentions);
+        self
+    }
+
     /// Send a message on a channel
     pub fn send_message(
         &self,
         message: &::MessageBuilder,
-        channel: &str,
+        channel: ChannelId,
         ) -> impl Future<Item = (), Error = Error> + Send {
-        message.to_request_body(channel)
-            .and_then(|body| {
-                // TODO: this is duped from send_message()
-                let auth_value = format!("Bot {}", self.token);
-                let auth_value_ref: &str = &auth_value;
-                hyper::Request::post(format!(
-                        "https://discordapp.com/api/v6/channels/{}/messages",
-                        channel
-                        )).header(hyper::header::AUTHORIZATION, auth_value_ref)
-                    .header(hyper::header::CONTENT_TYPE, "application/json")
-                    .header(hyper::header::CONTENT_LENGTH, body.len())
-                    .body(body.into())
-                    .map_err(|e| Error::Other(format!("Failed to create request: {:?}", e)))
-            })
-        .and_then(|req| {
-            Ok(self.http_client.request(req)
-               .map_err(|e| e.into()))
-        })
+        let body = try_future!(message.to_request_body(&self.default_allowed_mentions));
+        self.send_message_body(channel, body).into()
+    }
+
+    /// Open a DM channel with a user, or fetch the existing one
+    pub fn create_dm(&self, user_id: UserId) -> impl Future<Item = Channel, Error = Error> + Send {
+        #[derive(Serialize)]
+        struct CreateDMBody {
+            recipient_id: UserId,
+        }
+        let body = try_future!(to_body(&CreateDMBody { recipient_id: user_id }));
+        self.request_json(hyper::Method::POST, "/users/@me/channels", Some(body), None)
+            .into()
+    }
+
+    /// Send a direct message to a user, reusing the DM channel if one was already opened
+    pub fn send_dm(
+        &self,
+        user_id: UserId,
+        message: &::MessageBuilder,
+        ) -> impl Future<Item = (), Error = Error> + Send {
+        let body = try_future!(message.to_request_body(&self.default_allowed_mentions));
+        let cached = self.dm_channels.lock().unwrap().get(&user_id).cloned();
+        match cached {
+            Some(channel_id) => {
+                futures::future::Either::A(self.send_message_body(channel_id, body))
+            }
+            None => {
+                let client = self.clone();
+                futures::future::Either::B(self.create_dm(user_id).and_then(move |channel| {
+                    client
+                        .dm_channels
+                        .lock()
+                        .unwrap()
+                        .insert(user_id, channel.id);
+                    client.send_message_body(channel.id, body)
+                }))
+            }
+        }.into()
+    }
+
+    fn send_message_body(
+        &self,
+        channel: ChannelId,
+        body: String,
+        ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::POST,
+            &format!("/channels/{}/messages", channel),
+            Some(body),
+            None,
+            )
+    }
+
+    fn request(
+        &self,
+        method: hyper::Method,
+        path: &str,
+        body: Option<String>,
+        reason: Option<&str>,
+        ) -> impl Future<Item = hyper::Chunk, Error = Error> + Send {
+        let auth_value = format!("Bot {}", self.token);
+        request(&self.http_client, Some(&auth_value), method, path, body, reason)
+    }
+
+    fn request_json<T>(
+        &self,
+        method: hyper::Method,
+        path: &str,
+        body: Option<String>,
+        reason: Option<&str>,
+        ) -> impl Future<Item = T, Error = Error> + Send
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        self.request(method, path, body, reason).and_then(parse_response)
+    }
+
+    fn request_empty(
+        &self,
+        method: hyper::Method,
+        path: &str,
+        body: Option<String>,
+        reason: Option<&str>,
+        ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request(method, path, body, reason).map(|_| ())
+    }
+}
+
+type HttpsClient = hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>;
+
+fn new_http_client() -> Result<HttpsClient, Error> {
+    hyper_tls::HttpsConnector::new(1)
+        .map(|connector| hyper::Client::builder().build(connector))
+        .map_err(|e| e.into())
+}
+
+/// Sends a request to the API, resolving to the response body if the request succeeded
+fn request(
+    http_client: &HttpsClient,
+    auth_value: Option<&str>,
+    method: hyper::Method,
+    path: &str,
+    body: Option<String>,
+    reason: Option<&str>,
+    ) -> impl Future<Item = hyper::Chunk, Error = Error> + Send {
+    let span = debug_span!(
+        "request",
+        id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed),
+        method = %method,
+        path = %redact_path(path),
+    );
+    let mut builder = hyper::Request::builder();
+    builder
+        .method(method)
+        .uri(format!("{}{}", API_BASE, path));
+    if let Some(auth_value) = auth_value {
+        builder.header(hyper::header::AUTHORIZATION, auth_value);
+    }
+    if let Some(reason) = reason {
+        builder.header("X-Audit-Log-Reason", &*encode_reason(reason));
+    }
+    let req = match body {
+        Some(body) => builder
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .header(hyper::header::CONTENT_LENGTH, body.len())
+            .body(body.into()),
+        None => builder
+            .header(hyper::header::CONTENT_LENGTH, 0)
+            .body(Default::default()),
+    };
+    let http_client = http_client.clone();
+    req.map_err(|e| Error::Other(format!("Failed to create request: {:?}", e)))
         .into_future()
-            .and_then(|x| x)
-            .and_then(|resp| -> Box<Future<Item = (), Error = Error> + Send> {
-                match resp.status() {
-                    hyper::StatusCode::OK => Box::new(futures::future::ok(())),
-                    _ => Box::new(resp.into_body().concat2().map_err(|e| e.into()).and_then(
-                            |body| {
-                                Err(Error::Other(format!(
-                                            "Message sending failed {}",
-                                            String::from_utf8_lossy(&body.to_vec())
-                                            )))
-                            },
-                            )),
-                }
-            })
+        .and_then(move |req| http_client.request(req).map_err(|e| e.into()))
+        .and_then(|resp| {
+            let status = resp.status();
+            resp.into_body()
+                .concat2()
+                .map_err(|e| e.into())
+                .and_then(move |body| {
+                    if status.is_success() {
+                        debug!(status = status.as_u16(), "Request succeeded");
+                        Ok(body)
+                    } else if status == hyper::StatusCode::UNAUTHORIZED {
+                        warn!(status = status.as_u16(), "Request was not authorized");
+                        Err(Error::AuthenticationFailed)
+                    } else {
+                        warn!(status = status.as_u16(), "Request failed");
+                        Err(Error::Other(format!(
+                            "Request failed with status {}: {}",
+                            status,
+                            String::from_utf8_lossy(&body)
+                        )))
+                    }
+                })
+        })
+        .instrument(span)
+}
+
+/// Hides the token in webhook paths, so they can be logged
+fn redact_path(path: &str) -> String {
+    // "/webhooks/{id}/{token}/..." splits into ["", "webhooks", id, token, ...]
+    let mut segments: Vec<&str> = path.split('/').collect();
+    if segments.len() > 3 && segments[1] == "webhooks" {
+        let token = segments[3];
+        let query = &token[token.find('?').unwrap_or(token.len())..];
+        let redacted = format!("{{token}}{}", query);
+        segments[3] = &redacted;
+        return segments.join("/");
+    }
+    path.to_owned()
+}
+
+fn parse_response<T: serde::de::DeserializeOwned>(body: hyper::Chunk) -> Result<T, Error> {
+    serde_json::from_slice(&body)
+        .map_err(|e| Error::Other(format!("Failed to parse API response: {:?}", e)))
+}
+
+fn to_body<T: serde::Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string(value)
+        .map_err(|e| Error::Other(format!("Failed to serialize request body: {:?}", e)))
+}
+
+/// Percent-encodes an audit log reason for use in a header
+fn encode_reason(reason: &str) -> String {
+    reason
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => url::percent_encoding::percent_encode_byte(byte).to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_reason;
+
+    #[test]
+    fn encode_reason_keeps_unreserved_characters() {
+        assert_eq!(encode_reason("Spam-bot_1.0~"), "Spam-bot_1.0~");
+    }
+
+    #[test]
+    fn encode_reason_escapes_everything_else() {
+        assert_eq!(encode_reason("too many pings!"), "too%20many%20pings%21");
+        assert_eq!(encode_reason("a\r\nb"), "a%0D%0Ab");
+        assert_eq!(encode_reason("100%"), "100%25");
+        assert_eq!(encode_reason("né ✓"), "n%C3%A9%20%E2%9C%93");
     }
 }
@@ -1,4 +1,6 @@
 use futures;
+use rand;
+use serde;
 use serde_json;
 use std;
 use tokio;
@@ -6,25 +8,58 @@ use url;
 use tokio_tungstenite;
 use tokio_tungstenite::tungstenite;
 
-use events;
 use {Error, Event};
 
+use futures::sync::oneshot;
 use futures::{Future, Sink, Stream};
+use rand::Rng;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::executor::Executor;
+use tracing_futures::Instrument;
+
+/// Close codes after which reconnecting can't succeed
+const FATAL_CLOSE_CODES: &[u16] = &[4004, 4010, 4011, 4012, 4013, 4014];
+
+/// Close codes after which the session can't be resumed
+const SESSION_CLOSE_CODES: &[u16] = &[4007, 4009];
+
+/// ID of the next gateway connection, used to tell connections apart in logs
+static NEXT_CONNECTION_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// Stream of gateway events
+///
+/// Reconnects automatically when the connection is lost, resuming the session where possible.
+/// After an error reconnecting can't fix, such as an invalid token, the error is returned
+/// and the stream ends.
 pub struct GatewayConnection {
     token: String,
     url: url::Url,
     state: ConnectionState,
+    session_info: Arc<Mutex<Option<ReconnectInfo>>>,
+    queued: VecDeque<Event>,
+    /// Stops the heartbeat task of the current connection, which holds the write half of its socket
+    stop_heartbeat: oneshot::Sender<()>,
 }
 
 enum ConnectionState {
-    Pending(Box<Future<Item = ConnectionState, Error = Error> + Send>),
-    Connected(Box<Stream<Item = Event, Error = Error> + Send>),
+    Pending(Box<dyn Future<Item = ConnectionState, Error = Error> + Send>),
+    Connected(Box<dyn Stream<Item = GatewayItem, Error = Error> + Send>),
+    /// Closed for good after an error reconnecting can't fix
+    Closed,
+}
+
+/// Result of handling a single packet
+#[allow(clippy::large_enum_variant)]
+enum GatewayItem {
+    Event(Event),
+    Reconnect(String),
+    ReconnectAfter(String, std::time::Duration),
+    Fatal(Error),
 }
 
+#[derive(Clone)]
 struct ReconnectInfo {
     session_id: String,
     last_event: u64,
@@ -33,25 +68,85 @@ struct ReconnectInfo {
 impl GatewayConnection {
     #[doc(hidden)]
     pub fn connect_new(url: url::Url, token: String) -> Self {
+        let session_info = Arc::new(Mutex::new(None));
+        let (stop_heartbeat, heartbeat_stopped) = oneshot::channel();
         Self {
-            state: ConnectionState::Pending(GatewayConnection::connect(&token, &url, None)),
+            state: ConnectionState::Pending(GatewayConnection::connect(
+                &token,
+                &url,
+                session_info.clone(),
+                heartbeat_stopped,
+            )),
             url,
             token,
+            session_info,
+            queued: VecDeque::new(),
+            stop_heartbeat,
         }
     }
 
+    /// Replace the current connection, optionally after a delay, queueing an event describing why
+    fn reconnect(&mut self, reason: String, delay: Option<std::time::Duration>) {
+        warn!(reason = reason.as_str(), ?delay, "Reconnecting to gateway");
+        self.queued.push_back(Event::Reconnecting { reason });
+        let heartbeat_stopped = self.stop_heartbeat();
+        self.state = ConnectionState::Pending(match delay {
+            None => GatewayConnection::connect(
+                &self.token,
+                &self.url,
+                self.session_info.clone(),
+                heartbeat_stopped,
+            ),
+            Some(delay) => {
+                let token = self.token.clone();
+                let url = self.url.clone();
+                let session_info = self.session_info.clone();
+                Box::new(
+                    tokio::timer::Delay::new(std::time::Instant::now() + delay)
+                        .map_err(|e| Error::Other(format!("Timer error: {:?}", e)))
+                        .and_then(move |_| {
+                            GatewayConnection::connect(
+                                &token,
+                                &url,
+                                session_info,
+                                heartbeat_stopped,
+                            )
+                        }),
+                )
+            }
+        });
+    }
+
+    /// Stop the heartbeat task of the current connection, returning the receiver for the next one
+    ///
+    /// The old connection is closed once both its heartbeat task and its stream are dropped.
+    fn stop_heartbeat(&mut self) -> oneshot::Receiver<()> {
+        let (stop_heartbeat, heartbeat_stopped) = oneshot::channel();
+        let _ = std::mem::replace(&mut self.stop_heartbeat, stop_heartbeat).send(());
+        heartbeat_stopped
+    }
+
     fn connect(
         token: &str,
         url: &url::Url,
-        resume_info: Option<ReconnectInfo>,
-    ) -> Box<Future<Item = ConnectionState, Error = Error> + Send> {
+        session_info: Arc<Mutex<Option<ReconnectInfo>>>,
+        heartbeat_stopped: oneshot::Receiver<()>,
+    ) -> Box<dyn Future<Item = ConnectionState, Error = Error> + Send> {
         let token = token.to_owned();
+        let resume_info = session_info.lock().unwrap().clone();
+        let span = info_span!(
+            "gateway_connection",
+            id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+            resume = resume_info.is_some(),
+        );
+        let stream_span = span.clone();
+        let heartbeat_span = span.clone();
         Box::new(
             tokio_tungstenite::connect_async(url.clone())
                 .map_err(|e| e.into())
                 .and_then(|(socket, _)| socket.into_future().map_err(|(e, _)| e.into()))
                 .and_then(
-                    move |(msg1, socket)| -> Box<Future<Item = _, Error = _> + Send> {
+                    move |(msg1, socket)| -> Box<dyn Future<Item = _, Error = _> + Send> {
                         #[derive(Deserialize)]
                         struct Hello {
                             pub heartbeat_interval: u64,
@@ -87,15 +182,19 @@ impl GatewayConnection {
                                     Error::Other(format!("Failed to parse hello message: {:?}", e))
                                 }));
                             let first_packet = match resume_info {
-                                Some(info) => serde_json::to_string(&::DiscordBasePayload {
-                                    op: 6,
-                                    d: Resume {
-                                        token: &token,
-                                        session_id: &info.session_id,
-                                        seq: info.last_event,
-                                    },
-                                }),
+                                Some(info) => {
+                                    info!(seq = info.last_event, "Resuming session");
+                                    serde_json::to_string(&::DiscordBasePayload {
+                                        op: 6,
+                                        d: Resume {
+                                            token: &token,
+                                            session_id: &info.session_id,
+                                            seq: info.last_event,
+                                        },
+                                    })
+                                }
                                 None => {
+                                    info!("Identifying");
                                     serde_json::to_string(&::DiscordBasePayload {
                                         op: 2,
                                         d: Identify {
@@ -130,8 +229,6 @@ impl GatewayConnection {
                     },
                 )
                 .and_then(|(socket, hello)| {
-                    let session_info: Arc<Mutex<Option<ReconnectInfo>>> =
-                        Arc::new(Mutex::new(None));
                     let (sink, stream) = socket.split();
                     let heartbeat_stream = tokio::timer::Interval::new(
                         std::time::Instant::now(),
@@ -149,29 +246,42 @@ impl GatewayConnection {
                                         tungstenite::Message::Text(
                                             json!({
                                                        "op": 1,
-                                                       "d": match *session_info_hb.lock().unwrap() {
-                                                           Some(ref info) => {
-                                                               Some(info.last_event)
-                                                           },
-                                                           None => None
-                                                       }
+                                                       "d": session_info_hb.lock().unwrap()
+                                                           .as_ref()
+                                                           .map(|info| info.last_event)
                                                    }).to_string(),
                                         )
                                     }),
                             ).map(|_| ())
                                 .map_err(|e| {
-                                    eprintln!("Websocket error in heartbeat stream: {:?}", e);
-                                }),
+                                    warn!(error = ?e, "Websocket error in heartbeat stream");
+                                })
+                                .select(heartbeat_stopped.then(|_| Ok(())))
+                                .map(|_| ())
+                                .map_err(|_| ())
+                                .instrument(heartbeat_span),
                         ))
                         .map_err(|e| {
                             Error::Other(format!("Failed to spawn heartbeat stream: {:?}", e))
                         })?;
                     Ok(ConnectionState::Connected(Box::new(
                         stream
-                            .map_err(|e| e.into())
-                            .filter_map(move |packet| handle_packet(&session_info, packet)),
+                            .then(move |result| -> Result<_, Error> {
+                                match result {
+                                    Ok(packet) => Ok(handle_packet(&session_info, packet)),
+                                    Err(tungstenite::Error::ConnectionClosed(frame)) => {
+                                        let close_code = frame.map(|frame| frame.code.into());
+                                        Ok(handle_close(&session_info, close_code))
+                                    }
+                                    Err(err) => Err(err.into()),
+                                }
+                            })
+                            .map(futures::stream::iter_ok)
+                            .flatten()
+                            .instrument(stream_span),
                     )))
-                }),
+                })
+                .instrument(span),
         )
     }
 }
@@ -181,42 +291,48 @@ impl Stream for GatewayConnection {
     type Error = Error;
 
     fn poll(&mut self) -> futures::Poll<Option<Self::Item>, Error> {
-        enum ConnPollRes {
-            NewState(ConnectionState),
-            Result(futures::Poll<Option<Event>, Error>),
-        }
-        let res =
-            match self.state {
-                ConnectionState::Pending(ref mut fut) => {
-                    let res = fut.poll();
-                    match res {
-                        Ok(futures::Async::Ready(new_state)) => ConnPollRes::NewState(new_state),
-                        Ok(futures::Async::NotReady) => {
-                            ConnPollRes::Result(Ok(futures::Async::NotReady))
-                        }
-                        Err(err) => ConnPollRes::Result(Err(err)),
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                return Ok(futures::Async::Ready(Some(event)));
+            }
+            let (reason, delay) = match self.state {
+                ConnectionState::Pending(ref mut fut) => match fut.poll()? {
+                    futures::Async::Ready(new_state) => {
+                        self.state = new_state;
+                        continue;
                     }
-                }
-                ConnectionState::Connected(ref mut stream) => {
-                    let res = stream.poll();
-                    match res {
-                        Ok(futures::Async::Ready(None)) => {
-                            // stream ended, reconnect
-                            println!("reconnecting");
-                            ConnPollRes::NewState(ConnectionState::Pending(
-                                GatewayConnection::connect(&self.token, &self.url, None),
-                            ))
-                        }
-                        other => ConnPollRes::Result(other),
+                    futures::Async::NotReady => return Ok(futures::Async::NotReady),
+                },
+                ConnectionState::Connected(ref mut stream) => match stream.poll() {
+                    Ok(futures::Async::Ready(Some(GatewayItem::Event(event)))) => {
+                        return Ok(futures::Async::Ready(Some(event)))
                     }
-                }
+                    Ok(futures::Async::Ready(Some(GatewayItem::Reconnect(reason)))) => {
+                        (reason, None)
+                    }
+                    Ok(futures::Async::Ready(Some(GatewayItem::ReconnectAfter(reason, delay)))) => {
+                        (reason, Some(delay))
+                    }
+                    Ok(futures::Async::Ready(Some(GatewayItem::Fatal(err)))) => {
+                        drop(self.stop_heartbeat());
+                        self.state = ConnectionState::Closed;
+                        return Err(err);
+                    }
+                    Ok(futures::Async::Ready(None)) => {
+                        self.queued
+                            .push_back(Event::Disconnected { close_code: None });
+                        ("Connection closed without a close frame".to_owned(), None)
+                    }
+                    Ok(futures::Async::NotReady) => return Ok(futures::Async::NotReady),
+                    Err(err) => {
+                        self.queued
+                            .push_back(Event::Disconnected { close_code: None });
+                        (format!("Websocket error: {:?}", err), None)
+                    }
+                },
+                ConnectionState::Closed => return Ok(futures::Async::Ready(None)),
             };
-        match res {
-            ConnPollRes::NewState(state) => {
-                self.state = state;
-                self.poll()
-            }
-            ConnPollRes::Result(res) => res,
+            self.reconnect(reason, delay);
         }
     }
 }
@@ -224,76 +340,193 @@ impl Stream for GatewayConnection {
 fn handle_packet(
     session_info: &Arc<Mutex<Option<ReconnectInfo>>>,
     msg: tungstenite::Message,
-) -> Option<Event> {
-    if let tungstenite::Message::Text(text) = msg {
-        #[derive(Deserialize)]
-        struct RecvPayload<'a> {
-            pub op: u8,
-            pub d: serde_json::Value,
-            pub s: Option<u64>,
-            pub t: Option<&'a str>,
-        }
-        match serde_json::from_str::<RecvPayload>(&text) {
-            Err(err) => {
-                eprintln!("Failed to parse packet: {:?}", err);
-                None
+) -> Vec<GatewayItem> {
+    match msg {
+        tungstenite::Message::Text(text) => {
+            #[derive(Deserialize)]
+            struct RecvPayload<'a> {
+                pub op: u8,
+                pub d: serde_json::Value,
+                pub s: Option<u64>,
+                pub t: Option<&'a str>,
             }
-            Ok(packet) => {
-                match packet.op {
-                    0 => {
-                        if let Some(seq) = packet.s {
-                            if let Some(ref mut info) = *session_info.lock().unwrap() {
-                                info.last_event = seq;
+            match serde_json::from_str::<RecvPayload>(&text) {
+                Err(err) => {
+                    warn!(error = %err, "Failed to parse packet");
+                    vec![]
+                }
+                Ok(packet) => {
+                    match packet.op {
+                        0 => {
+                            if let Some(seq) = packet.s {
+                                if let Some(ref mut info) = *session_info.lock().unwrap() {
+                                    info.last_event = seq;
+                                }
                             }
-                        }
-                        match packet.t {
-                            Some(ref t) => handle_event(&t, packet.d),
-                            None => {
-                                eprintln!("Missing event type");
-                                None
+                            match packet.t {
+                                Some(t) => {
+                                    let event = handle_event(t, packet.d);
+                                    if let Event::Ready(ref data) = event {
+                                        info!(
+                                            session_id = data.session_id.as_str(),
+                                            "Session ready"
+                                        );
+                                        *session_info.lock().unwrap() = Some(ReconnectInfo {
+                                            session_id: data.session_id.clone(),
+                                            last_event: packet.s.unwrap_or(0),
+                                        });
+                                    }
+                                    vec![GatewayItem::Event(event)]
+                                }
+                                None => {
+                                    warn!("Missing event type");
+                                    vec![]
+                                }
                             }
                         }
-                    }
-                    11 => {
-                        // heartbeat ACK
-                        // potentially useful, but ignored for now
-                        None
-                    }
-                    op => {
-                        eprintln!("Unrecognized packet op: {}", op);
-                        None
+                        7 => {
+                            info!("Discord requested a reconnect");
+                            vec![GatewayItem::Reconnect(
+                                "Discord requested a reconnect".to_owned(),
+                            )]
+                        }
+                        9 => {
+                            let resumable = packet.d.as_bool().unwrap_or(false);
+                            warn!(resumable, "Session was invalidated");
+                            let reason = "Session was invalidated".to_owned();
+                            let reconnect = if resumable {
+                                GatewayItem::Reconnect(reason)
+                            } else {
+                                *session_info.lock().unwrap() = None;
+                                // Discord requires waiting 1-5 seconds before identifying again
+                                let delay = rand::thread_rng().gen_range(1000, 5001);
+                                GatewayItem::ReconnectAfter(
+                                    reason,
+                                    std::time::Duration::from_millis(delay),
+                                )
+                            };
+                            vec![GatewayItem::Event(Event::InvalidSession { resumable }), reconnect]
+                        }
+                        11 => {
+                            trace!("Heartbeat acknowledged");
+                            vec![]
+                        }
+                        op => {
+                            debug!(op, "Unrecognized packet op");
+                            vec![]
+                        }
                     }
                 }
             }
         }
-    } else {
-        eprintln!("Unexpected message type: {:?}", msg);
-        None
+        msg => {
+            debug!(message = ?msg, "Unexpected message type");
+            vec![]
+        }
     }
 }
 
-fn handle_event(t: &str, d: serde_json::Value) -> Option<Event> {
-    match t {
-        "READY" => {
-            let user = match serde_json::from_value(d["user"].clone()) {
-                Err(err) => {
-                    eprintln!("Failed to parse ready user: {:?}", err);
-                    return None;
-                }
-                Ok(x) => x,
-            };
-            Some(Event::Ready(events::ReadyData { user }))
+fn handle_close(
+    session_info: &Arc<Mutex<Option<ReconnectInfo>>>,
+    close_code: Option<u16>,
+) -> Vec<GatewayItem> {
+    warn!(?close_code, "Gateway closed the connection");
+    let disconnected = GatewayItem::Event(Event::Disconnected { close_code });
+    match close_code {
+        Some(4004) => {
+            error!("Gateway rejected the token");
+            vec![disconnected, GatewayItem::Fatal(Error::AuthenticationFailed)]
+        }
+        Some(code) if FATAL_CLOSE_CODES.contains(&code) => {
+            error!(code, "Gateway closed the connection with a fatal code");
+            vec![
+                disconnected,
+                GatewayItem::Fatal(Error::Other(format!(
+                    "Gateway closed the connection with code {}",
+                    code
+                ))),
+            ]
         }
-        "MESSAGE_CREATE" => match serde_json::from_value(d) {
-            Err(err) => {
-                eprintln!("Failed to parse message: {:?}", err);
-                None
+        Some(code) => {
+            if SESSION_CLOSE_CODES.contains(&code) {
+                *session_info.lock().unwrap() = None;
             }
-            Ok(data) => Some(Event::MessageCreate(data)),
-        },
+            vec![
+                disconnected,
+                GatewayItem::Reconnect(format!("Connection closed with code {}", code)),
+            ]
+        }
+        None => vec![
+            disconnected,
+            GatewayItem::Reconnect("Connection closed".to_owned()),
+        ],
+    }
+}
+
+fn handle_event(t: &str, d: serde_json::Value) -> Event {
+    match t {
+        "READY" => parse_event(t, d, Event::Ready),
+        "RESUMED" => {
+            info!("Session resumed");
+            Event::Resumed
+        }
+        "MESSAGE_CREATE" => parse_event(t, d, Event::MessageCreate),
+        "MESSAGE_UPDATE" => parse_event(t, d, Event::MessageUpdate),
+        "MESSAGE_DELETE" => parse_event(t, d, Event::MessageDelete),
+        "MESSAGE_DELETE_BULK" => parse_event(t, d, Event::MessageDeleteBulk),
+        "GUILD_CREATE" => parse_event(t, d, Event::GuildCreate),
+        "GUILD_UPDATE" => parse_event(t, d, Event::GuildUpdate),
+        "GUILD_DELETE" => parse_event(t, d, Event::GuildDelete),
+        "GUILD_MEMBER_ADD" => parse_event(t, d, Event::GuildMemberAdd),
+        "GUILD_MEMBER_UPDATE" => parse_event(t, d, Event::GuildMemberUpdate),
+        "GUILD_MEMBER_REMOVE" => parse_event(t, d, Event::GuildMemberRemove),
+        "GUILD_MEMBERS_CHUNK" => parse_event(t, d, Event::GuildMembersChunk),
+        "PRESENCE_UPDATE" => parse_event(t, d, Event::PresenceUpdate),
+        "USER_UPDATE" => parse_event(t, d, Event::UserUpdate),
+        "CHANNEL_CREATE" => parse_event(t, d, Event::ChannelCreate),
+        "CHANNEL_UPDATE" => parse_event(t, d, Event::ChannelUpdate),
+        "CHANNEL_DELETE" => parse_event(t, d, Event::ChannelDelete),
+        "CHANNEL_PINS_UPDATE" => parse_event(t, d, Event::ChannelPinsUpdate),
+        "GUILD_ROLE_CREATE" => parse_event(t, d, Event::GuildRoleCreate),
+        "GUILD_ROLE_UPDATE" => parse_event(t, d, Event::GuildRoleUpdate),
+        "GUILD_ROLE_DELETE" => parse_event(t, d, Event::GuildRoleDelete),
+        "GUILD_EMOJIS_UPDATE" => parse_event(t, d, Event::GuildEmojisUpdate),
+        "GUILD_BAN_ADD" => parse_event(t, d, Event::GuildBanAdd),
+        "GUILD_BAN_REMOVE" => parse_event(t, d, Event::GuildBanRemove),
+        "THREAD_CREATE" => parse_event(t, d, Event::ThreadCreate),
+        "THREAD_UPDATE" => parse_event(t, d, Event::ThreadUpdate),
+        "THREAD_DELETE" => parse_event(t, d, Event::ThreadDelete),
+        "THREAD_LIST_SYNC" => parse_event(t, d, Event::ThreadListSync),
+        "THREAD_MEMBERS_UPDATE" => parse_event(t, d, Event::ThreadMembersUpdate),
+        "MESSAGE_REACTION_ADD" => parse_event(t, d, Event::ReactionAdd),
+        "MESSAGE_REACTION_REMOVE" => parse_event(t, d, Event::ReactionRemove),
+        "MESSAGE_REACTION_REMOVE_ALL" => parse_event(t, d, Event::ReactionRemoveAll),
+        "MESSAGE_REACTION_REMOVE_EMOJI" => parse_event(t, d, Event::ReactionRemoveEmoji),
+        "TYPING_START" => parse_event(t, d, Event::TypingStart),
         _ => {
-            eprintln!("Unrecognized event type: {}", t);
-            None
+            debug!(event = t, "Unknown event");
+            Event::Unknown {
+                name: t.to_owned(),
+                data: d,
+            }
+        }
+    }
+}
+
+fn parse_event<T, F>(t: &str, d: serde_json::Value, variant: F) -> Event
+where
+    T: serde::de::DeserializeOwned,
+    F: FnOnce(T) -> Event,
+{
+    match T::deserialize(&d) {
+        Err(err) => {
+            error!(event = t, error = %err, "Failed to parse event");
+            Event::ParseFailed {
+                name: t.to_owned(),
+                data: d,
+                error: err.to_string(),
+            }
         }
+        Ok(data) => variant(data),
     }
 }
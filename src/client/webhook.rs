@@ -0,0 +1,248 @@
+use hyper;
+use serde;
+use url;
+
+use builder::{AllowedMentions, WebhookBuilder, WebhookMessageBuilder};
+use events::{ChannelId, GuildId, MessageId, ReceivedMessage, Snowflake, Webhook};
+use Error;
+
+use futures::Future;
+
+use super::{new_http_client, parse_response, request, to_body, Client, HttpsClient};
+
+#[derive(Clone)]
+/// Object used to send messages through a webhook, without a bot token or gateway connection
+pub struct WebhookClient {
+    http_client: HttpsClient,
+    id: Snowflake,
+    token: String,
+    default_allowed_mentions: AllowedMentions,
+}
+
+impl WebhookClient {
+    /// Create a client for the webhook with the given ID and token
+    pub fn new(id: Snowflake, token: &str) -> Result<Self, Error> {
+        Ok(WebhookClient {
+            http_client: new_http_client()?,
+            id,
+            token: token.to_owned(),
+            default_allowed_mentions: AllowedMentions::default(),
+        })
+    }
+
+    /// Create a client from a webhook URL, like `https://discordapp.com/api/webhooks/{id}/{token}`
+    pub fn from_url(webhook_url: &str) -> Result<Self, Error> {
+        let parsed = url::Url::parse(webhook_url)
+            .map_err(|e| Error::Other(format!("Unable to parse webhook URL: {:?}", e)))?;
+        let mut segments = parsed
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .skip_while(|segment| *segment != "webhooks")
+            .skip(1);
+        match (segments.next(), segments.next()) {
+            (Some(id), Some(token)) if !token.is_empty() => {
+                let id = id
+                    .parse()
+                    .map_err(|e| Error::Other(format!("Invalid webhook ID in URL: {:?}", e)))?;
+                WebhookClient::new(id, token)
+            }
+            _ => Err(Error::Other(format!(
+                "Webhook URL is missing an ID or token: {}",
+                webhook_url
+            ))),
+        }
+    }
+
+    /// Create a client for a webhook fetched or created through the bot API
+    pub fn from_webhook(webhook: &Webhook) -> Result<Self, Error> {
+        match webhook.token {
+            Some(ref token) => WebhookClient::new(webhook.id, token),
+            None => Err(Error::Other(format!(
+                "Webhook {} has no token",
+                webhook.id
+            ))),
+        }
+    }
+
+    /// Set which mentions may ping in messages that don't set their own
+    /// [`AllowedMentions`](../builder/struct.AllowedMentions.html).
+    /// Defaults to allowing everything except `@everyone` and `@here`.
+    pub fn set_default_allowed_mentions(&mut self, allowed_mentions: AllowedMentions) {
+        self.default_allowed_mentions = allowed_mentions;
+    }
+
+    /// Set which mentions may ping in messages that don't set their own
+    /// [`AllowedMentions`](../builder/struct.AllowedMentions.html)
+    pub fn with_default_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.set_default_allowed_mentions(allowed_mentions);
+        self
+    }
+
+    /// Fetch information about this webhook
+    pub fn get_info(&self) -> impl Future<Item = Webhook, Error = Error> + Send {
+        self.request_json(hyper::Method::GET, "", None)
+    }
+
+    /// Send a message through this webhook
+    pub fn execute(
+        &self,
+        message: &WebhookMessageBuilder,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        let body = try_future!(message.to_request_body(&self.default_allowed_mentions));
+        self.request(hyper::Method::POST, "", Some(body))
+            .map(|_| ())
+            .into()
+    }
+
+    /// Send a message through this webhook, waiting for Discord to return the created message
+    pub fn execute_and_wait(
+        &self,
+        message: &WebhookMessageBuilder,
+    ) -> impl Future<Item = ReceivedMessage, Error = Error> + Send {
+        let body = try_future!(message.to_request_body(&self.default_allowed_mentions));
+        self.request_json(hyper::Method::POST, "?wait=true", Some(body))
+            .into()
+    }
+
+    /// Modify a message previously sent by this webhook. Username and avatar overrides are ignored.
+    pub fn edit_message(
+        &self,
+        message_id: MessageId,
+        message: &WebhookMessageBuilder,
+    ) -> impl Future<Item = ReceivedMessage, Error = Error> + Send {
+        let body = try_future!(message.to_request_body(&self.default_allowed_mentions));
+        self.request_json(
+            hyper::Method::PATCH,
+            &format!("/messages/{}", message_id),
+            Some(body),
+        ).into()
+    }
+
+    /// Delete a message previously sent by this webhook
+    pub fn delete_message(
+        &self,
+        message_id: MessageId,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request(
+            hyper::Method::DELETE,
+            &format!("/messages/{}", message_id),
+            None,
+        ).map(|_| ())
+    }
+
+    fn request(
+        &self,
+        method: hyper::Method,
+        path: &str,
+        body: Option<String>,
+    ) -> impl Future<Item = hyper::Chunk, Error = Error> + Send {
+        request(
+            &self.http_client,
+            None,
+            method,
+            &format!("/webhooks/{}/{}{}", self.id, self.token, path),
+            body,
+            None,
+        )
+    }
+
+    fn request_json<T>(
+        &self,
+        method: hyper::Method,
+        path: &str,
+        body: Option<String>,
+    ) -> impl Future<Item = T, Error = Error> + Send
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        self.request(method, path, body).and_then(parse_response)
+    }
+}
+
+impl Client {
+    /// Create a webhook on a channel
+    pub fn create_webhook(
+        &self,
+        channel_id: ChannelId,
+        webhook: &WebhookBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Webhook, Error = Error> + Send {
+        let body = try_future!(to_body(webhook));
+        self.request_json(
+            hyper::Method::POST,
+            &format!("/channels/{}/webhooks", channel_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// List the webhooks on a channel
+    pub fn get_channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Item = Vec<Webhook>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/channels/{}/webhooks", channel_id),
+            None,
+            None,
+        )
+    }
+
+    /// List the webhooks in a guild
+    pub fn get_guild_webhooks(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Future<Item = Vec<Webhook>, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/guilds/{}/webhooks", guild_id),
+            None,
+            None,
+        )
+    }
+
+    /// Fetch a webhook by ID
+    pub fn get_webhook(
+        &self,
+        webhook_id: Snowflake,
+    ) -> impl Future<Item = Webhook, Error = Error> + Send {
+        self.request_json(
+            hyper::Method::GET,
+            &format!("/webhooks/{}", webhook_id),
+            None,
+            None,
+        )
+    }
+
+    /// Modify a webhook's name, avatar or channel
+    pub fn edit_webhook(
+        &self,
+        webhook_id: Snowflake,
+        webhook: &WebhookBuilder,
+        reason: Option<&str>,
+    ) -> impl Future<Item = Webhook, Error = Error> + Send {
+        let body = try_future!(to_body(webhook));
+        self.request_json(
+            hyper::Method::PATCH,
+            &format!("/webhooks/{}", webhook_id),
+            Some(body),
+            reason,
+        ).into()
+    }
+
+    /// Delete a webhook
+    pub fn delete_webhook(
+        &self,
+        webhook_id: Snowflake,
+        reason: Option<&str>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.request_empty(
+            hyper::Method::DELETE,
+            &format!("/webhooks/{}", webhook_id),
+            None,
+            reason,
+        )
+    }
+}
@@ -1,4 +1,5 @@
 use hyper;
+use std;
 use hyper_tls;
 use tokio_tungstenite::tungstenite;
 
@@ -8,6 +9,10 @@ quick_error! {
     pub enum Error {
         /// Failed to authenticate with the API
         AuthenticationFailed {}
+        /// A message or embed broke one of Discord's documented limits
+        LimitExceeded(err: LimitError) {
+            display("{}", err)
+        }
         /// Some other error
         Other(e: String) {}
     }
@@ -30,3 +35,24 @@ impl From<tungstenite::Error> for Error {
         Error::Other(format!("WebSocket Failure: {:?}", e))
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Details of an [`Error::LimitExceeded`]
+pub struct LimitError {
+    /// Path of the offending field, like `embed.fields[3].value`
+    pub field: String,
+    /// Maximum allowed length or count
+    pub limit: usize,
+    /// Actual length or count
+    pub actual: usize,
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} exceeds the limit of {} (was {})",
+            self.field, self.limit, self.actual
+        )
+    }
+}
@@ -1,43 +1,857 @@
-/// Type used for IDs
-pub type Snowflake = String;
+use chrono;
+use serde;
+use serde_json;
+use std;
 
-#[derive(Debug)]
+use builder::EmbedBuilder;
+use fmt;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Point in time, parsed from the ISO8601 strings Discord uses
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// First millisecond of 2015, the epoch of Discord's snowflake timestamps
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// Unique ID used by Discord for every object. ([relevant Discord docs](https://discordapp.com/developers/docs/reference#snowflakes))
+///
+/// Sent by Discord as a string, but accepted from integers as well.
+pub struct Snowflake(pub u64);
+
+impl Snowflake {
+    /// Milliseconds since the Unix epoch at which this ID was created
+    pub fn timestamp_millis(self) -> u64 {
+        (self.0 >> 22) + DISCORD_EPOCH_MS
+    }
+
+    /// Time at which this ID was created
+    pub fn timestamp(self) -> Timestamp {
+        (UNIX_EPOCH + Duration::from_millis(self.timestamp_millis())).into()
+    }
+
+    /// ID of the internal worker that generated this ID
+    pub fn worker_id(self) -> u8 {
+        ((self.0 >> 17) & 0x1F) as u8
+    }
+
+    /// ID of the internal process that generated this ID
+    pub fn process_id(self) -> u8 {
+        ((self.0 >> 12) & 0x1F) as u8
+    }
+
+    /// Counter incremented for every ID generated by the process
+    pub fn increment(self) -> u16 {
+        (self.0 & 0xFFF) as u16
+    }
+}
+
+impl From<u64> for Snowflake {
+    fn from(value: u64) -> Self {
+        Snowflake(value)
+    }
+}
+
+impl From<Snowflake> for u64 {
+    fn from(value: Snowflake) -> u64 {
+        value.0
+    }
+}
+
+impl std::str::FromStr for Snowflake {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Snowflake)
+    }
+}
+
+impl std::fmt::Display for Snowflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Snowflake {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SnowflakeVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SnowflakeVisitor {
+            type Value = Snowflake;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a snowflake as a string or integer")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Snowflake, E> {
+                Ok(Snowflake(value))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Snowflake, E> {
+                if value < 0 {
+                    Err(E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+                } else {
+                    Ok(Snowflake(value as u64))
+                }
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Snowflake, E> {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(SnowflakeVisitor)
+    }
+}
+
+impl serde::Serialize for Snowflake {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+macro_rules! id_type {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident;
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+        pub struct $name(pub Snowflake);
+
+        impl $name {
+            /// Time at which this ID was created
+            pub fn timestamp(self) -> Timestamp {
+                self.0.timestamp()
+            }
+        }
+
+        impl From<Snowflake> for $name {
+            fn from(value: Snowflake) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                $name(Snowflake(value))
+            }
+        }
+
+        impl From<$name> for Snowflake {
+            fn from(value: $name) -> Snowflake {
+                value.0
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map($name)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+id_type! {
+    /// ID of a user
+    pub struct UserId;
+}
+
+id_type! {
+    /// ID of a channel
+    pub struct ChannelId;
+}
+
+id_type! {
+    /// ID of a guild
+    pub struct GuildId;
+}
+
+id_type! {
+    /// ID of a message
+    pub struct MessageId;
+}
+
+id_type! {
+    /// ID of a role
+    pub struct RoleId;
+}
+
+macro_rules! int_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $value:expr,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            /// Value not known to this library
+            Unknown(u64),
+        }
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Unknown(other),
+                }
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> u64 {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(other) => other,
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <u64 as serde::Deserialize>::deserialize(deserializer).map($name::from)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u64((*self).into())
+            }
+        }
+    };
+}
+
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
 /// Known events that may be received
 pub enum Event {
     /// Connection established
     Ready(ReadyData),
     /// Message received
     MessageCreate(ReceivedMessage),
+    /// Message edited, or embeds added to it
+    MessageUpdate(MessageUpdateData),
+    /// Message deleted
+    MessageDelete(MessageDeleteData),
+    /// Multiple messages deleted at once
+    MessageDeleteBulk(MessageDeleteBulkData),
+    /// Guild became available, either lazily after connecting, after an outage, or when the user joined it
+    GuildCreate(Guild),
+    /// Guild settings changed
+    GuildUpdate(Guild),
+    /// Guild became unavailable due to an outage, or the user left or was removed from it
+    GuildDelete(UnavailableGuild),
+    /// User joined a guild
+    GuildMemberAdd(GuildMemberAddData),
+    /// Member's nickname, roles or other guild-specific info changed
+    GuildMemberUpdate(GuildMemberUpdateData),
+    /// User left or was removed from a guild
+    GuildMemberRemove(GuildMemberRemoveData),
+    /// Members requested through the gateway
+    GuildMembersChunk(GuildMembersChunkData),
+    /// User's status or activities changed
+    PresenceUpdate(Presence),
+    /// Current user's account info changed
+    UserUpdate(Myself),
+    /// Guild channel created, or DM opened
+    ChannelCreate(Channel),
+    /// Channel settings changed
+    ChannelUpdate(Channel),
+    /// Guild channel deleted, or DM closed
+    ChannelDelete(Channel),
+    /// Message pinned or unpinned
+    ChannelPinsUpdate(ChannelPinsUpdateData),
+    /// Role created
+    GuildRoleCreate(GuildRoleData),
+    /// Role settings changed
+    GuildRoleUpdate(GuildRoleData),
+    /// Role deleted
+    GuildRoleDelete(GuildRoleDeleteData),
+    /// Custom emojis of a guild changed
+    GuildEmojisUpdate(GuildEmojisUpdateData),
+    /// User banned from a guild
+    GuildBanAdd(GuildBanData),
+    /// User unbanned from a guild
+    GuildBanRemove(GuildBanData),
+    /// Thread created, or the current user was added to a private thread
+    ThreadCreate(Channel),
+    /// Thread settings changed
+    ThreadUpdate(Channel),
+    /// Thread deleted
+    ThreadDelete(ThreadDeleteData),
+    /// Active threads sent after gaining access to a channel
+    ThreadListSync(ThreadListSyncData),
+    /// Users added to or removed from a thread
+    ThreadMembersUpdate(ThreadMembersUpdateData),
+    /// User reacted to a message
+    ReactionAdd(ReactionData),
+    /// User removed their reaction from a message
+    ReactionRemove(ReactionData),
+    /// All reactions removed from a message
+    ReactionRemoveAll(ReactionRemoveAllData),
+    /// All reactions with a specific emoji removed from a message
+    ReactionRemoveEmoji(ReactionRemoveEmojiData),
+    /// User started typing
+    TypingStart(TypingStartData),
+    /// Session resumed after reconnecting, with missed events replayed
+    Resumed,
+    /// Connection lost or reset, a new one is being established
+    Reconnecting {
+        /// Why the connection is being reestablished
+        reason: String,
+    },
+    /// Connection closed
+    Disconnected {
+        /// Websocket close code, if the connection was closed cleanly. ([relevant Discord docs](https://discordapp.com/developers/docs/topics/opcodes-and-status-codes#gateway-gateway-close-event-codes))
+        close_code: Option<u16>,
+    },
+    /// Session invalidated by Discord. If it can't be resumed, a fresh [`Event::Ready`] follows and caches should be rebuilt.
+    InvalidSession {
+        /// Whether the session can be resumed
+        resumable: bool,
+    },
+    /// Dispatch not known to this library, passed through so it can be handled manually
+    Unknown {
+        /// Event name, like `MESSAGE_CREATE`
+        name: String,
+        /// Raw event payload
+        data: serde_json::Value,
+    },
+    /// Known dispatch whose payload could not be parsed
+    ParseFailed {
+        /// Event name, like `MESSAGE_CREATE`
+        name: String,
+        /// Raw event payload
+        data: serde_json::Value,
+        /// Description of the parse error
+        error: String,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize)]
 /// Object contained in [`Event::Ready`]
 pub struct ReadyData {
     /// Authenticated user info
     pub user: Myself,
+    /// Guilds the user is in, which are sent later through [`Event::GuildCreate`]
+    pub guilds: Vec<UnavailableGuild>,
+    /// ID of the gateway session, used for resuming
+    pub session_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildMemberAdd`]
+pub struct GuildMemberAddData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// New member
+    #[serde(flatten)]
+    pub member: Member,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildMemberUpdate`]
+pub struct GuildMemberUpdateData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// User the member represents
+    pub user: User,
+    /// Guild-specific nickname
+    pub nick: Option<String>,
+    /// IDs of the member's roles
+    pub roles: Vec<RoleId>,
+    /// Time the user joined the guild
+    pub joined_at: Option<Timestamp>,
+    /// Time the user started boosting the guild
+    pub premium_since: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildMemberRemove`]
+pub struct GuildMemberRemoveData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// User that was removed
+    pub user: User,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildMembersChunk`]
+pub struct GuildMembersChunkData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// Members in this chunk
+    pub members: Vec<Member>,
+    /// Index of this chunk, starting at 0
+    pub chunk_index: u64,
+    /// Total number of chunks for the request
+    pub chunk_count: u64,
+    /// Requested user IDs that weren't found
+    #[serde(default)]
+    pub not_found: Vec<UserId>,
+    /// Presences of the members, if requested
+    #[serde(default)]
+    pub presences: Vec<Presence>,
+    /// Nonce given in the request
+    pub nonce: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ChannelPinsUpdate`]
+pub struct ChannelPinsUpdateData {
+    /// ID of the guild, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+    /// ID of the channel
+    pub channel_id: ChannelId,
+    /// Time the most recent message was pinned
+    pub last_pin_timestamp: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildRoleCreate`] and [`Event::GuildRoleUpdate`]
+pub struct GuildRoleData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// Created or updated role
+    pub role: Role,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildRoleDelete`]
+pub struct GuildRoleDeleteData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// ID of the deleted role
+    pub role_id: RoleId,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildEmojisUpdate`]
+pub struct GuildEmojisUpdateData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// All custom emojis of the guild after the change
+    pub emojis: Vec<Emoji>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::GuildBanAdd`] and [`Event::GuildBanRemove`]
+pub struct GuildBanData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// User that was banned or unbanned
+    pub user: User,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ThreadDelete`]
+pub struct ThreadDeleteData {
+    /// ID of the thread
+    pub id: ChannelId,
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// ID of the channel the thread was in
+    pub parent_id: Option<ChannelId>,
+    /// Type of thread
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ThreadListSync`]
+pub struct ThreadListSyncData {
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// IDs of the channels being synced, or empty for the whole guild
+    #[serde(default)]
+    pub channel_ids: Vec<ChannelId>,
+    /// Active threads in the synced channels
+    pub threads: Vec<Channel>,
+    /// Thread memberships of the current user in the synced threads
+    pub members: Vec<ThreadMember>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ThreadMembersUpdate`]
+pub struct ThreadMembersUpdateData {
+    /// ID of the thread
+    pub id: ChannelId,
+    /// ID of the guild
+    pub guild_id: GuildId,
+    /// Approximate number of members in the thread, stopping at 50
+    pub member_count: u64,
+    /// Users added to the thread
+    #[serde(default)]
+    pub added_members: Vec<ThreadMember>,
+    /// IDs of users removed from the thread
+    #[serde(default)]
+    pub removed_member_ids: Vec<UserId>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ReactionAdd`] and [`Event::ReactionRemove`]
+pub struct ReactionData {
+    /// ID of the user who reacted
+    pub user_id: UserId,
+    /// ID of the channel containing the message
+    pub channel_id: ChannelId,
+    /// ID of the message
+    pub message_id: MessageId,
+    /// ID of the guild, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+    /// Guild member info for the user. Only sent for [`Event::ReactionAdd`] in guilds.
+    pub member: Option<Member>,
+    /// Partial emoji, with only ID, name and animated set
+    pub emoji: Emoji,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ReactionRemoveAll`]
+pub struct ReactionRemoveAllData {
+    /// ID of the channel containing the message
+    pub channel_id: ChannelId,
+    /// ID of the message
+    pub message_id: MessageId,
+    /// ID of the guild, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::ReactionRemoveEmoji`]
+pub struct ReactionRemoveEmojiData {
+    /// ID of the channel containing the message
+    pub channel_id: ChannelId,
+    /// ID of the message
+    pub message_id: MessageId,
+    /// ID of the guild, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+    /// Partial emoji, with only ID, name and animated set
+    pub emoji: Emoji,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::TypingStart`]
+pub struct TypingStartData {
+    /// ID of the channel
+    pub channel_id: ChannelId,
+    /// ID of the guild, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+    /// ID of the user
+    pub user_id: UserId,
+    /// Time the user started typing
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub timestamp: Timestamp,
+    /// Guild member info for the user, if typing in a guild
+    pub member: Option<Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::MessageUpdate`]. Fields that weren't sent are `None`.
+pub struct MessageUpdateData {
+    /// Message ID
+    pub id: MessageId,
+    /// ID of the channel containing the message
+    pub channel_id: ChannelId,
+    /// ID of the guild containing the message, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+    /// Text content of the message
+    pub content: Option<String>,
+    /// Author of the message
+    pub author: Option<User>,
+    /// Time the message was edited
+    pub edited_timestamp: Option<Timestamp>,
+    /// Users specifically mentioned in the message
+    pub mentions: Option<Vec<User>>,
+    /// IDs of roles specifically mentioned in the message
+    pub mention_roles: Option<Vec<RoleId>>,
+    /// Whether the message mentions `@everyone` or `@here`
+    pub mention_everyone: Option<bool>,
+    /// Files attached to the message
+    pub attachments: Option<Vec<Attachment>>,
+    /// Embeds in the message
+    pub embeds: Option<Vec<EmbedBuilder>>,
+    /// Whether the message is pinned
+    pub pinned: Option<bool>,
+    /// [Message flags](https://discordapp.com/developers/docs/resources/channel#message-object-message-flags) as a bitfield
+    pub flags: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::MessageDelete`]
+pub struct MessageDeleteData {
+    /// ID of the deleted message
+    pub id: MessageId,
+    /// ID of the channel containing the message
+    pub channel_id: ChannelId,
+    /// ID of the guild containing the message, or `None` for direct messages
+    pub guild_id: Option<GuildId>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Object contained in [`Event::MessageDeleteBulk`]
+pub struct MessageDeleteBulkData {
+    /// IDs of the deleted messages
+    pub ids: Vec<MessageId>,
+    /// ID of the channel containing the messages
+    pub channel_id: ChannelId,
+    /// ID of the guild containing the messages
+    pub guild_id: Option<GuildId>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
 /// Message received from a channel
 pub struct ReceivedMessage {
     /// Message ID
-    pub id: Snowflake,
+    pub id: MessageId,
     /// ID of the origin channel
-    pub channel_id: Snowflake,
+    pub channel_id: ChannelId,
+    /// ID of the origin guild, or `None` for direct messages.
+    /// Only sent with gateway events, so always `None` for messages returned by REST calls.
+    pub guild_id: Option<GuildId>,
     /// Text content of the message
     pub content: String,
     /// Whether this is a TTS message
     pub tts: bool,
     /// Author of the message
     pub author: User,
-    timestamp: String,
+    /// Time the message was sent
+    pub timestamp: Timestamp,
+    /// Users specifically mentioned in the message
+    #[serde(default)]
+    pub mentions: Vec<User>,
+    /// IDs of roles specifically mentioned in the message
+    #[serde(default)]
+    pub mention_roles: Vec<RoleId>,
+    /// Whether the message mentions `@everyone` or `@here`
+    #[serde(default)]
+    pub mention_everyone: bool,
+    /// Channels mentioned in a crossposted message. Empty for normal messages.
+    #[serde(default)]
+    pub mention_channels: Vec<ChannelMention>,
+    /// Guild member info for the author, if sent in a guild
+    pub member: Option<PartialMember>,
+    /// Files attached to the message
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Embeds in the message, either sent by the author or generated from links
+    #[serde(default)]
+    pub embeds: Vec<EmbedBuilder>,
+    /// Reactions on the message
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+    /// Whether the message is pinned
+    #[serde(default)]
+    pub pinned: bool,
+    /// ID of the webhook that sent the message, if any
+    pub webhook_id: Option<Snowflake>,
+    /// Type of message
+    #[serde(rename = "type")]
+    pub kind: MessageType,
+    /// [Message flags](https://discordapp.com/developers/docs/resources/channel#message-object-message-flags) as a bitfield
+    #[serde(default)]
+    pub flags: u64,
+    /// Time the message was last edited
+    pub edited_timestamp: Option<Timestamp>,
+    /// Source of a reply, crosspost or pin notification
+    pub message_reference: Option<MessageReference>,
+    /// Message being replied to, or `None` if it was deleted or this is not a reply
+    pub referenced_message: Option<Box<ReceivedMessage>>,
+    /// Stickers sent with the message
+    #[serde(default, rename = "sticker_items")]
+    pub stickers: Vec<StickerItem>,
+    /// Value used by the sender to confirm the message was sent, either a string or an integer
+    pub nonce: Option<serde_json::Value>,
+}
+
+impl ReceivedMessage {
+    /// Whether this message was sent in a direct message channel
+    ///
+    /// Only meaningful for messages received from gateway events. Messages returned by REST calls
+    /// have no `guild_id`, so this is always `true` for them.
+    pub fn is_dm(&self) -> bool {
+        self.guild_id.is_none()
+    }
+
+    /// Whether the given user is specifically mentioned in this message
+    pub fn mentions_user(&self, user_id: UserId) -> bool {
+        self.mentions.iter().any(|user| user.id == user_id)
+    }
+
+    /// Split the content into text, mentions, custom emoji and timestamps
+    pub fn tokens(&self) -> Vec<fmt::Token<'_>> {
+        fmt::tokenize(&self.content)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Channel mentioned in a crossposted message. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#channel-mention-object))
+pub struct ChannelMention {
+    /// Channel ID
+    pub id: ChannelId,
+    /// ID of the guild containing the channel
+    pub guild_id: GuildId,
+    /// Type of channel
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    /// Channel name
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Guild member info attached to a message, without the user. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/guild#guild-member-object))
+pub struct PartialMember {
+    /// Guild-specific nickname
+    pub nick: Option<String>,
+    /// IDs of the member's roles
+    #[serde(default)]
+    pub roles: Vec<RoleId>,
+    /// Time the user joined the guild
+    pub joined_at: Option<Timestamp>,
+    /// Whether the member is deafened in voice channels
+    #[serde(default)]
+    pub deaf: bool,
+    /// Whether the member is muted in voice channels
+    #[serde(default)]
+    pub mute: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// File attached to a message. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#attachment-object))
+pub struct Attachment {
+    /// Attachment ID
+    pub id: Snowflake,
+    /// Name of the file
+    pub filename: String,
+    /// [Media type](https://en.wikipedia.org/wiki/Media_type) of the file
+    pub content_type: Option<String>,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Source URL of the file
+    pub url: String,
+    /// Proxied URL of the file
+    pub proxy_url: String,
+    /// Height of the file, if it is an image
+    pub height: Option<u64>,
+    /// Width of the file, if it is an image
+    pub width: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Reaction on a message. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#reaction-object))
+pub struct Reaction {
+    /// Number of times this emoji has been used to react
+    pub count: u64,
+    /// Whether the current user reacted with this emoji
+    pub me: bool,
+    /// Partial emoji, with only ID, name and animated set
+    pub emoji: Emoji,
 }
 
-#[derive(Debug, Deserialize)]
+int_enum! {
+    /// Type of a message. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#message-object-message-types))
+    pub enum MessageType {
+        /// Normal message
+        Default = 0,
+        /// User added to a group DM
+        RecipientAdd = 1,
+        /// User removed from a group DM
+        RecipientRemove = 2,
+        /// Call started
+        Call = 3,
+        /// Channel name changed
+        ChannelNameChange = 4,
+        /// Channel icon changed
+        ChannelIconChange = 5,
+        /// Message pinned
+        ChannelPinnedMessage = 6,
+        /// Member joined the guild
+        GuildMemberJoin = 7,
+        /// Member boosted the guild
+        UserPremiumGuildSubscription = 8,
+        /// Boost brought the guild to tier 1
+        UserPremiumGuildSubscriptionTier1 = 9,
+        /// Boost brought the guild to tier 2
+        UserPremiumGuildSubscriptionTier2 = 10,
+        /// Boost brought the guild to tier 3
+        UserPremiumGuildSubscriptionTier3 = 11,
+        /// Announcement channel followed
+        ChannelFollowAdd = 12,
+        /// Guild removed from discovery
+        GuildDiscoveryDisqualified = 14,
+        /// Guild back in discovery
+        GuildDiscoveryRequalified = 15,
+        /// Reply to another message
+        Reply = 19,
+        /// Response to a slash command
+        ChatInputCommand = 20,
+        /// First message of a thread, pointing to the message it was started from
+        ThreadStarterMessage = 21,
+        /// Reminder to invite people to the guild
+        GuildInviteReminder = 22,
+        /// Response to a context menu command
+        ContextMenuCommand = 23,
+    }
+}
+
+int_enum! {
+    /// File format of a sticker. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/sticker#sticker-object-sticker-format-types))
+    pub enum StickerFormatType {
+        /// PNG image
+        Png = 1,
+        /// Animated PNG image
+        Apng = 2,
+        /// Lottie animation
+        Lottie = 3,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Sticker sent with a message. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/sticker#sticker-item-object))
+pub struct StickerItem {
+    /// Sticker ID
+    pub id: Snowflake,
+    /// Sticker name
+    pub name: String,
+    /// File format of the sticker
+    pub format_type: StickerFormatType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Reference to another message, as used by replies and crossposts. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#message-object-message-reference-structure))
+pub struct MessageReference {
+    /// ID of the referenced message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    /// ID of the channel of the referenced message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// ID of the guild of the referenced message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// Whether to fail sending a reply if the referenced message doesn't exist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_if_not_exists: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
 /// Data about the current user. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/user#user-object))
 pub struct Myself {
     /// User ID
-    pub id: Snowflake,
+    pub id: UserId,
     /// Username, not unique
     pub username: String,
     /// 4-digit Discord tag
@@ -55,11 +869,11 @@ pub struct Myself {
     pub email: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 /// Data about a Discord User. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/user#user-object))
 pub struct User {
     /// User ID
-    pub id: Snowflake,
+    pub id: UserId,
     /// Username, not unique
     pub username: String,
     /// 4-digit Discord tag
@@ -68,13 +882,775 @@ pub struct User {
     pub avatar: Option<String>,
 }
 
-impl Into<User> for Myself {
-    fn into(self) -> User {
+impl From<Myself> for User {
+    fn from(myself: Myself) -> User {
         User {
-            id: self.id,
-            username: self.username,
-            discriminator: self.discriminator,
-            avatar: self.avatar,
+            id: myself.id,
+            username: myself.username,
+            discriminator: myself.discriminator,
+            avatar: myself.avatar,
+        }
+    }
+}
+
+int_enum! {
+    /// Type of a channel. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#channel-object-channel-types))
+    pub enum ChannelType {
+        /// Text channel in a guild
+        GuildText = 0,
+        /// Direct message between users
+        DM = 1,
+        /// Voice channel in a guild
+        GuildVoice = 2,
+        /// Direct message between multiple users
+        GroupDM = 3,
+        /// Category containing other channels
+        GuildCategory = 4,
+        /// Announcement channel that can be followed
+        GuildNews = 5,
+        /// Store page channel
+        GuildStore = 6,
+        /// Thread in an announcement channel
+        GuildNewsThread = 10,
+        /// Public thread in a text channel
+        GuildPublicThread = 11,
+        /// Private thread in a text channel
+        GuildPrivateThread = 12,
+        /// Voice channel for hosting events with an audience
+        GuildStageVoice = 13,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Data about a channel. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#channel-object))
+pub struct Channel {
+    /// Channel ID
+    pub id: ChannelId,
+    /// Type of channel
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    /// ID of the guild, if this is a guild channel
+    pub guild_id: Option<GuildId>,
+    /// Sorting position
+    pub position: Option<i64>,
+    /// Explicit permission overwrites for members and roles
+    #[serde(default)]
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// Channel name
+    pub name: Option<String>,
+    /// Channel topic
+    pub topic: Option<String>,
+    /// Whether the channel is NSFW
+    #[serde(default)]
+    pub nsfw: bool,
+    /// ID of the last message sent in this channel
+    pub last_message_id: Option<MessageId>,
+    /// Bitrate of a voice channel, in bits
+    pub bitrate: Option<u64>,
+    /// User limit of a voice channel
+    pub user_limit: Option<u64>,
+    /// Slowmode delay, in seconds
+    pub rate_limit_per_user: Option<u64>,
+    /// Recipients of a DM
+    #[serde(default)]
+    pub recipients: Vec<User>,
+    /// Icon hash of a group DM
+    pub icon: Option<String>,
+    /// ID of the group DM creator
+    pub owner_id: Option<UserId>,
+    /// ID of the parent category
+    pub parent_id: Option<ChannelId>,
+    /// Time the last message was pinned
+    pub last_pin_timestamp: Option<Timestamp>,
+    /// Approximate number of messages in a thread, stopping at 50
+    pub message_count: Option<u64>,
+    /// Approximate number of users in a thread, stopping at 50
+    pub member_count: Option<u64>,
+    /// Thread-specific settings
+    pub thread_metadata: Option<ThreadMetadata>,
+    /// Current user's membership of a thread, if they joined it
+    pub member: Option<ThreadMember>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Thread-specific settings of a [`Channel`]. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#thread-metadata-object))
+pub struct ThreadMetadata {
+    /// Whether the thread is archived
+    pub archived: bool,
+    /// Minutes of inactivity after which the thread is archived
+    pub auto_archive_duration: u64,
+    /// Time the archive status was last changed
+    pub archive_timestamp: Timestamp,
+    /// Whether only moderators can unarchive the thread
+    #[serde(default)]
+    pub locked: bool,
+    /// Whether non-moderators can add others to a private thread
+    pub invitable: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// User's membership of a thread. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#thread-member-object))
+pub struct ThreadMember {
+    /// ID of the thread, omitted inside [`Guild`] and [`Channel`]
+    pub id: Option<ChannelId>,
+    /// ID of the user, omitted inside [`Guild`] and [`Channel`]
+    pub user_id: Option<UserId>,
+    /// Time the user last joined the thread
+    pub join_timestamp: Timestamp,
+    /// Notification settings
+    pub flags: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// Target type of a [`PermissionOverwrite`]
+pub enum OverwriteType {
+    /// Overwrite applies to a role
+    Role,
+    /// Overwrite applies to a single member
+    Member,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Permission overwrite for a channel. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/channel#overwrite-object))
+pub struct PermissionOverwrite {
+    /// ID of the role or user
+    pub id: Snowflake,
+    /// Whether `id` refers to a role or a user
+    #[serde(rename = "type")]
+    pub kind: OverwriteType,
+    /// Bitset of allowed permissions
+    pub allow: u64,
+    /// Bitset of denied permissions
+    pub deny: u64,
+}
+
+impl PermissionOverwrite {
+    /// Create an overwrite for a role
+    pub fn role(id: Snowflake, allow: u64, deny: u64) -> Self {
+        PermissionOverwrite {
+            id,
+            kind: OverwriteType::Role,
+            allow,
+            deny,
+        }
+    }
+
+    /// Create an overwrite for a single member
+    pub fn member(id: Snowflake, allow: u64, deny: u64) -> Self {
+        PermissionOverwrite {
+            id,
+            kind: OverwriteType::Member,
+            allow,
+            deny,
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize)]
+/// Invite to a guild or channel. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/invite#invite-object))
+pub struct Invite {
+    /// Invite code, unique
+    pub code: String,
+    /// Guild this invite is for
+    pub guild: Option<InviteGuild>,
+    /// Channel this invite is for
+    pub channel: InviteChannel,
+    /// User who created the invite
+    pub inviter: Option<User>,
+    /// Number of times the invite has been used
+    pub uses: Option<u64>,
+    /// Maximum number of uses, or 0 for unlimited
+    pub max_uses: Option<u64>,
+    /// Duration in seconds after which the invite expires, or 0 for never
+    pub max_age: Option<u64>,
+    /// Whether the invite only grants temporary membership
+    #[serde(default)]
+    pub temporary: bool,
+    /// Time the invite was created
+    pub created_at: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Partial guild contained in an [`Invite`]
+pub struct InviteGuild {
+    /// Guild ID
+    pub id: GuildId,
+    /// Guild name
+    pub name: String,
+    /// Guild [icon hash](https://discordapp.com/developers/docs/reference#image-formatting)
+    pub icon: Option<String>,
+    /// Guild splash hash
+    pub splash: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Partial channel contained in an [`Invite`]
+pub struct InviteChannel {
+    /// Channel ID
+    pub id: ChannelId,
+    /// Channel name
+    pub name: Option<String>,
+    /// Type of channel
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+}
+
+int_enum! {
+    /// Verification required before members can talk. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/guild#guild-object-verification-level))
+    pub enum VerificationLevel {
+        /// Unrestricted
+        None = 0,
+        /// Must have a verified email
+        Low = 1,
+        /// Must also be registered for longer than 5 minutes
+        Medium = 2,
+        /// Must also be a member for longer than 10 minutes
+        High = 3,
+        /// Must also have a verified phone number
+        VeryHigh = 4,
+    }
+}
+
+int_enum! {
+    /// Default notification setting for members of a guild
+    pub enum MessageNotificationLevel {
+        /// Notify for every message
+        AllMessages = 0,
+        /// Notify only for mentions
+        OnlyMentions = 1,
+    }
+}
+
+int_enum! {
+    /// Whose messages are scanned for explicit content
+    pub enum ExplicitContentFilterLevel {
+        /// No scanning
+        Disabled = 0,
+        /// Scan messages from members without roles
+        MembersWithoutRoles = 1,
+        /// Scan messages from all members
+        AllMembers = 2,
+    }
+}
+
+int_enum! {
+    /// Whether moderators need two-factor authentication
+    pub enum MfaLevel {
+        /// No requirement
+        None = 0,
+        /// Moderators need 2FA enabled
+        Elevated = 1,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Data about a guild. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/guild#guild-object))
+pub struct Guild {
+    /// Guild ID
+    pub id: GuildId,
+    /// Guild name
+    pub name: String,
+    /// Guild [icon hash](https://discordapp.com/developers/docs/reference#image-formatting)
+    pub icon: Option<String>,
+    /// Guild splash hash
+    pub splash: Option<String>,
+    /// ID of the owner
+    pub owner_id: UserId,
+    /// Voice region ID, no longer sent by Discord for most guilds
+    pub region: Option<String>,
+    /// ID of the AFK voice channel
+    pub afk_channel_id: Option<ChannelId>,
+    /// AFK timeout, in seconds
+    pub afk_timeout: u64,
+    /// Verification required before members can talk
+    pub verification_level: VerificationLevel,
+    /// Default notification setting
+    pub default_message_notifications: MessageNotificationLevel,
+    /// Explicit content filter setting
+    pub explicit_content_filter: ExplicitContentFilterLevel,
+    /// Roles in the guild
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    /// Custom emojis in the guild
+    #[serde(default)]
+    pub emojis: Vec<Emoji>,
+    /// Enabled guild features
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Required MFA level for moderators
+    pub mfa_level: MfaLevel,
+    /// Application ID of the creator, if created by a bot
+    pub application_id: Option<Snowflake>,
+    /// ID of the channel where system messages are sent
+    pub system_channel_id: Option<ChannelId>,
+    /// Description of a discoverable guild
+    pub description: Option<String>,
+    /// Guild banner hash
+    pub banner: Option<String>,
+    /// Vanity invite code
+    pub vanity_url_code: Option<String>,
+    /// Server boost level
+    #[serde(default)]
+    pub premium_tier: u64,
+    /// Number of server boosts
+    pub premium_subscription_count: Option<u64>,
+    /// Maximum number of members
+    pub max_members: Option<u64>,
+    /// Time the current user joined the guild. Only sent in [`Event::GuildCreate`].
+    pub joined_at: Option<Timestamp>,
+    /// Whether the guild is considered large. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub large: bool,
+    /// Whether the guild is unavailable due to an outage
+    #[serde(default)]
+    pub unavailable: bool,
+    /// Total number of members. Only sent in [`Event::GuildCreate`].
+    pub member_count: Option<u64>,
+    /// Voice connections in the guild. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub voice_states: Vec<VoiceState>,
+    /// Members of the guild, possibly incomplete for large guilds. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub members: Vec<Member>,
+    /// Channels in the guild. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+    /// Presences of members, possibly incomplete for large guilds. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub presences: Vec<Presence>,
+    /// Active threads the current user can see. Only sent in [`Event::GuildCreate`].
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Guild that is not yet or no longer available. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/guild#unavailable-guild-object))
+pub struct UnavailableGuild {
+    /// Guild ID
+    pub id: GuildId,
+    /// Whether the guild is unavailable due to an outage. If false in [`Event::GuildDelete`], the user was removed from the guild.
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// User's connection to a voice channel. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/voice#voice-state-object))
+pub struct VoiceState {
+    /// ID of the guild, omitted inside [`Guild::voice_states`]
+    pub guild_id: Option<GuildId>,
+    /// ID of the connected channel, or `None` if disconnected
+    pub channel_id: Option<ChannelId>,
+    /// ID of the user
+    pub user_id: UserId,
+    /// Guild member info for the user
+    pub member: Option<Member>,
+    /// ID of the voice session
+    pub session_id: String,
+    /// Whether the user is deafened by the guild
+    pub deaf: bool,
+    /// Whether the user is muted by the guild
+    pub mute: bool,
+    /// Whether the user deafened themselves
+    pub self_deaf: bool,
+    /// Whether the user muted themselves
+    pub self_mute: bool,
+    /// Whether the user is streaming
+    #[serde(default)]
+    pub self_stream: bool,
+    /// Whether the user's camera is on
+    #[serde(default)]
+    pub self_video: bool,
+    /// Whether the user's permission to speak is suppressed
+    pub suppress: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// Online status of a user
+pub enum Status {
+    /// Online
+    Online,
+    /// Away
+    Idle,
+    /// Do not disturb
+    Dnd,
+    /// Offline, or invisible to others
+    Offline,
+    /// Invisible, only sent for the current user
+    Invisible,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// User with only the ID guaranteed to be present
+pub struct PartialUser {
+    /// User ID
+    pub id: UserId,
+    /// Username, not unique
+    pub username: Option<String>,
+    /// 4-digit Discord tag
+    pub discriminator: Option<String>,
+    /// User's [avatar hash](https://discordapp.com/developers/docs/reference#image-formatting)
+    pub avatar: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// User's status in a guild. ([relevant Discord docs](https://discordapp.com/developers/docs/topics/gateway#presence-update))
+pub struct Presence {
+    /// User the presence is for
+    pub user: PartialUser,
+    /// ID of the guild, omitted inside [`Guild::presences`]
+    pub guild_id: Option<GuildId>,
+    /// Online status
+    pub status: Status,
+    /// Current activities, with the primary one first
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    /// Status on each platform
+    #[serde(default)]
+    pub client_status: ClientStatus,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+/// User's status on each platform, `None` where they aren't active
+pub struct ClientStatus {
+    /// Status on the desktop app
+    pub desktop: Option<Status>,
+    /// Status on the mobile app
+    pub mobile: Option<Status>,
+    /// Status on the web app or a bot
+    pub web: Option<Status>,
+}
+
+int_enum! {
+    /// Type of an activity. ([relevant Discord docs](https://discordapp.com/developers/docs/topics/gateway#activity-object-activity-types))
+    pub enum ActivityType {
+        /// "Playing {name}"
+        Playing = 0,
+        /// "Streaming {details}"
+        Streaming = 1,
+        /// "Listening to {name}"
+        Listening = 2,
+        /// "Watching {name}"
+        Watching = 3,
+        /// "{emoji} {state}"
+        Custom = 4,
+        /// "Competing in {name}"
+        Competing = 5,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Something a user is doing. ([relevant Discord docs](https://discordapp.com/developers/docs/topics/gateway#activity-object))
+pub struct Activity {
+    /// Name of the activity
+    pub name: String,
+    /// Type of activity
+    #[serde(rename = "type")]
+    pub kind: ActivityType,
+    /// Stream URL, for streaming activities
+    pub url: Option<String>,
+    /// Time the activity was added to the session, in milliseconds since the Unix epoch
+    pub created_at: Option<u64>,
+    /// Start and end of the activity
+    pub timestamps: Option<ActivityTimestamps>,
+    /// ID of the application running the activity
+    pub application_id: Option<Snowflake>,
+    /// What the user is currently doing
+    pub details: Option<String>,
+    /// User's current party status, or the text of a custom status
+    pub state: Option<String>,
+    /// Emoji of a custom status
+    pub emoji: Option<Emoji>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Start and end of an [`Activity`], in milliseconds since the Unix epoch
+pub struct ActivityTimestamps {
+    /// Time the activity started
+    pub start: Option<u64>,
+    /// Time the activity will end
+    pub end: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Role in a guild. ([relevant Discord docs](https://discordapp.com/developers/docs/topics/permissions#role-object))
+pub struct Role {
+    /// Role ID
+    pub id: RoleId,
+    /// Role name
+    pub name: String,
+    /// Integer representation of the role color
+    pub color: u32,
+    /// Whether the role is displayed separately in the member list
+    pub hoist: bool,
+    /// Sorting position
+    pub position: i64,
+    /// Bitset of permissions
+    pub permissions: u64,
+    /// Whether this role is managed by an integration
+    pub managed: bool,
+    /// Whether this role can be mentioned
+    pub mentionable: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Custom or unicode emoji. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/emoji#emoji-object))
+pub struct Emoji {
+    /// Emoji ID, or `None` for unicode emoji
+    pub id: Option<Snowflake>,
+    /// Emoji name, or the unicode character itself
+    pub name: Option<String>,
+    /// Roles allowed to use this emoji
+    #[serde(default)]
+    pub roles: Vec<RoleId>,
+    /// User that created this emoji
+    pub user: Option<User>,
+    /// Whether this emoji must be wrapped in colons
+    #[serde(default)]
+    pub require_colons: bool,
+    /// Whether this emoji is managed by an integration
+    #[serde(default)]
+    pub managed: bool,
+    /// Whether this emoji is animated
+    #[serde(default)]
+    pub animated: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Member of a guild. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/guild#guild-member-object))
+pub struct Member {
+    /// User this member represents
+    pub user: User,
+    /// Guild-specific nickname
+    pub nick: Option<String>,
+    /// IDs of the member's roles
+    pub roles: Vec<RoleId>,
+    /// Time the user joined the guild
+    pub joined_at: Timestamp,
+    /// Time the user started boosting the guild
+    pub premium_since: Option<Timestamp>,
+    /// Whether the member is deafened in voice channels
+    pub deaf: bool,
+    /// Whether the member is muted in voice channels
+    pub mute: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Ban in a guild
+pub struct Ban {
+    /// Reason given for the ban
+    pub reason: Option<String>,
+    /// Banned user
+    pub user: User,
+}
+
+int_enum! {
+    /// Type of action recorded in an audit log entry. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/audit-log#audit-log-entry-object-audit-log-events))
+    pub enum AuditLogEvent {
+        /// Guild settings were changed
+        GuildUpdate = 1,
+        /// Channel was created
+        ChannelCreate = 10,
+        /// Channel was modified
+        ChannelUpdate = 11,
+        /// Channel was deleted
+        ChannelDelete = 12,
+        /// Permission overwrite was added to a channel
+        ChannelOverwriteCreate = 13,
+        /// Permission overwrite was modified
+        ChannelOverwriteUpdate = 14,
+        /// Permission overwrite was removed
+        ChannelOverwriteDelete = 15,
+        /// Member was kicked
+        MemberKick = 20,
+        /// Inactive members were pruned
+        MemberPrune = 21,
+        /// Member was banned
+        MemberBanAdd = 22,
+        /// Ban was lifted
+        MemberBanRemove = 23,
+        /// Member was modified
+        MemberUpdate = 24,
+        /// Member's roles were changed
+        MemberRoleUpdate = 25,
+        /// Member was moved to another voice channel
+        MemberMove = 26,
+        /// Member was disconnected from a voice channel
+        MemberDisconnect = 27,
+        /// Bot was added to the guild
+        BotAdd = 28,
+        /// Role was created
+        RoleCreate = 30,
+        /// Role was modified
+        RoleUpdate = 31,
+        /// Role was deleted
+        RoleDelete = 32,
+        /// Invite was created
+        InviteCreate = 40,
+        /// Invite was modified
+        InviteUpdate = 41,
+        /// Invite was deleted
+        InviteDelete = 42,
+        /// Webhook was created
+        WebhookCreate = 50,
+        /// Webhook was modified
+        WebhookUpdate = 51,
+        /// Webhook was deleted
+        WebhookDelete = 52,
+        /// Emoji was created
+        EmojiCreate = 60,
+        /// Emoji was modified
+        EmojiUpdate = 61,
+        /// Emoji was deleted
+        EmojiDelete = 62,
+        /// Another user's message was deleted
+        MessageDelete = 72,
+        /// Messages were deleted in bulk
+        MessageBulkDelete = 73,
+        /// Message was pinned
+        MessagePin = 74,
+        /// Message was unpinned
+        MessageUnpin = 75,
+        /// Integration was added
+        IntegrationCreate = 80,
+        /// Integration was modified
+        IntegrationUpdate = 81,
+        /// Integration was removed
+        IntegrationDelete = 82,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Audit log of a guild. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/audit-log#audit-log-object))
+pub struct AuditLog {
+    /// Webhooks referenced by the entries
+    pub webhooks: Vec<Webhook>,
+    /// Users referenced by the entries
+    pub users: Vec<User>,
+    /// Entries, newest first
+    pub audit_log_entries: Vec<AuditLogEntry>,
+}
+
+impl AuditLog {
+    /// Look up a user referenced by an entry, such as its `user_id`
+    pub fn user(&self, id: UserId) -> Option<&User> {
+        self.users.iter().find(|user| user.id == id)
+    }
+
+    /// Look up a webhook referenced by an entry
+    pub fn webhook(&self, id: Snowflake) -> Option<&Webhook> {
+        self.webhooks.iter().find(|webhook| webhook.id == id)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Single action recorded in an [`AuditLog`]
+pub struct AuditLogEntry {
+    /// Entry ID
+    pub id: Snowflake,
+    /// ID of the affected entity
+    pub target_id: Option<Snowflake>,
+    /// Changes made to the target
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+    /// ID of the user who took the action, or `None` for system actions
+    pub user_id: Option<UserId>,
+    /// Type of action
+    pub action_type: AuditLogEvent,
+    /// Additional info for certain action types
+    pub options: Option<AuditLogEntryInfo>,
+    /// Reason given for the action
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Change to a single property recorded in an [`AuditLogEntry`]
+pub struct AuditLogChange {
+    /// Name of the changed property
+    pub key: String,
+    /// Value after the change
+    pub new_value: Option<serde_json::Value>,
+    /// Value before the change
+    pub old_value: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Additional info in an [`AuditLogEntry`], depending on the action type
+pub struct AuditLogEntryInfo {
+    /// Number of days after which inactive members were pruned
+    pub delete_member_days: Option<String>,
+    /// Number of members removed by a prune
+    pub members_removed: Option<String>,
+    /// Channel in which messages were deleted
+    pub channel_id: Option<ChannelId>,
+    /// Number of deleted messages
+    pub count: Option<String>,
+    /// ID of the overwritten role or member
+    pub id: Option<Snowflake>,
+    /// Type of the overwritten entity, "role" or "member"
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// Name of the overwritten role
+    pub role_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// Data about a webhook. ([relevant Discord docs](https://discordapp.com/developers/docs/resources/webhook#webhook-object))
+pub struct Webhook {
+    /// Webhook ID
+    pub id: Snowflake,
+    /// ID of the guild this webhook is for
+    pub guild_id: Option<GuildId>,
+    /// ID of the channel this webhook posts to, or `None` for application-owned webhooks
+    pub channel_id: Option<ChannelId>,
+    /// User that created this webhook
+    pub user: Option<User>,
+    /// Default name of the webhook
+    pub name: Option<String>,
+    /// Default [avatar hash](https://discordapp.com/developers/docs/reference#image-formatting)
+    pub avatar: Option<String>,
+    /// Secure token of the webhook
+    pub token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Example from Discord's documentation
+    const EXAMPLE: Snowflake = Snowflake(175928847299117063);
+
+    #[test]
+    fn snowflake_fields() {
+        assert_eq!(EXAMPLE.timestamp_millis(), 1462015105796);
+        assert_eq!(EXAMPLE.timestamp().timestamp_millis(), 1462015105796);
+        assert_eq!(EXAMPLE.worker_id(), 1);
+        assert_eq!(EXAMPLE.process_id(), 0);
+        assert_eq!(EXAMPLE.increment(), 7);
+        assert_eq!(Snowflake(0).timestamp_millis(), DISCORD_EPOCH_MS);
+    }
+
+    #[test]
+    fn snowflake_deserializes_from_string_or_integer() {
+        let from_str: Snowflake = serde_json::from_str("\"175928847299117063\"").unwrap();
+        let from_int: Snowflake = serde_json::from_str("175928847299117063").unwrap();
+        assert_eq!(from_str, EXAMPLE);
+        assert_eq!(from_int, EXAMPLE);
+        assert!(serde_json::from_str::<Snowflake>("-1").is_err());
+        assert!(serde_json::from_str::<Snowflake>("\"abc\"").is_err());
+        assert!(serde_json::from_str::<Snowflake>("1.5").is_err());
+    }
+
+    #[test]
+    fn snowflake_serializes_as_string() {
+        assert_eq!(
+            serde_json::to_string(&EXAMPLE).unwrap(),
+            "\"175928847299117063\""
+        );
+        let user: UserId = serde_json::from_str("\"175928847299117063\"").unwrap();
+        assert_eq!(user, UserId(EXAMPLE));
+        assert_eq!(serde_json::to_string(&user).unwrap(), "\"175928847299117063\"");
+        assert_eq!("42".parse::<ChannelId>().unwrap(), ChannelId(Snowflake(42)));
+    }
+}
@@ -0,0 +1,447 @@
+use std;
+
+use events::{ChannelId, RoleId, Snowflake, UserId};
+
+/// Characters with special meaning in Discord markdown
+const MARKDOWN_CHARS: &[char] = &['\\', '*', '_', '~', '|', '`', '>'];
+
+/// Zero-width space, used to break up sequences Discord would otherwise interpret
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
+
+/// Escape user input so it is displayed literally, with no formatting or pings
+pub fn escape(text: &str) -> String {
+    escape_mentions(&escape_markdown(text))
+}
+
+/// Escape markdown formatting characters, leaving mentions intact
+pub fn escape_markdown(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_CHARS.contains(&c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Escape user, role and channel mentions, and neutralize `@everyone` and `@here`
+pub fn escape_mentions(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '<' {
+            if let Some(&next) = chars.peek() {
+                if next == '@' || next == '#' {
+                    result.push('\\');
+                }
+            }
+        }
+        result.push(c);
+    }
+    sanitize_everyone(&result)
+}
+
+/// Neutralize `@everyone` and `@here` so they neither ping nor look like a ping
+pub fn sanitize_everyone(text: &str) -> String {
+    text.replace("@everyone", &format!("@{}everyone", ZERO_WIDTH_SPACE))
+        .replace("@here", &format!("@{}here", ZERO_WIDTH_SPACE))
+}
+
+/// Format text as **bold**
+pub fn bold(text: &str) -> String {
+    format!("**{}**", text)
+}
+
+/// Format text as *italic*
+pub fn italic(text: &str) -> String {
+    format!("*{}*", text)
+}
+
+/// Format text as underlined
+pub fn underline(text: &str) -> String {
+    format!("__{}__", text)
+}
+
+/// Format text as ~~strikethrough~~
+pub fn strikethrough(text: &str) -> String {
+    format!("~~{}~~", text)
+}
+
+/// Hide text behind a spoiler
+pub fn spoiler(text: &str) -> String {
+    format!("||{}||", text)
+}
+
+/// Format text as `inline code`, even if it contains backticks
+pub fn inline_code(text: &str) -> String {
+    if text.contains('`') {
+        format!("`` {} ``", separate_backticks(text))
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+/// Format text as a code block, optionally with a language for syntax highlighting
+pub fn code_block(language: Option<&str>, code: &str) -> String {
+    format!(
+        "```{}\n{}\n```",
+        language.unwrap_or(""),
+        separate_backticks(code)
+    )
+}
+
+/// Separate adjacent backticks, so no run of them can end a code span or block
+fn separate_backticks(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut prev = None;
+    for c in text.chars() {
+        if c == '`' && prev == Some('`') {
+            result.push(ZERO_WIDTH_SPACE);
+        }
+        result.push(c);
+        prev = Some(c);
+    }
+    result
+}
+
+/// Format text as a block quote, quoting every line
+pub fn block_quote(text: &str) -> String {
+    text.split('\n')
+        .map(|line| format!("> {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format a link with display text. Only rendered in embeds and webhook messages.
+pub fn masked_link(text: &str, url: &str) -> String {
+    format!("[{}]({})", text, url)
+}
+
+/// Mention a user
+pub fn user(user_id: UserId) -> String {
+    format!("<@{}>", user_id)
+}
+
+/// Mention a channel
+pub fn channel(channel_id: ChannelId) -> String {
+    format!("<#{}>", channel_id)
+}
+
+/// Mention a role
+pub fn role(role_id: RoleId) -> String {
+    format!("<@&{}>", role_id)
+}
+
+/// Display a custom emoji
+pub fn emoji(name: &str, emoji_id: Snowflake, animated: bool) -> String {
+    format!("<{}:{}:{}>", if animated { "a" } else { "" }, name, emoji_id)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How a [`timestamp`](fn.timestamp.html) is displayed
+pub enum TimestampStyle {
+    /// `16:20`
+    ShortTime,
+    /// `16:20:30`
+    LongTime,
+    /// `20/04/2021`
+    ShortDate,
+    /// `20 April 2021`
+    LongDate,
+    /// `20 April 2021 16:20`, the default
+    ShortDateTime,
+    /// `Tuesday, 20 April 2021 16:20`
+    LongDateTime,
+    /// `2 months ago`
+    Relative,
+}
+
+impl TimestampStyle {
+    fn from_flag(flag: char) -> Option<Self> {
+        match flag {
+            't' => Some(TimestampStyle::ShortTime),
+            'T' => Some(TimestampStyle::LongTime),
+            'd' => Some(TimestampStyle::ShortDate),
+            'D' => Some(TimestampStyle::LongDate),
+            'f' => Some(TimestampStyle::ShortDateTime),
+            'F' => Some(TimestampStyle::LongDateTime),
+            'R' => Some(TimestampStyle::Relative),
+            _ => None,
+        }
+    }
+
+    fn flag(self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::LongTime => 'T',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::ShortDateTime => 'f',
+            TimestampStyle::LongDateTime => 'F',
+            TimestampStyle::Relative => 'R',
+        }
+    }
+}
+
+impl std::fmt::Display for TimestampStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.flag())
+    }
+}
+
+/// Display a Unix timestamp in each reader's own timezone and locale
+pub fn timestamp(unix_seconds: i64, style: Option<TimestampStyle>) -> String {
+    match style {
+        Some(style) => format!("<t:{}:{}>", unix_seconds, style),
+        None => format!("<t:{}>", unix_seconds),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Piece of message content, as produced by [`tokenize`](fn.tokenize.html)
+pub enum Token<'a> {
+    /// Plain text, including any markdown
+    Text(&'a str),
+    /// User mention
+    User(UserId),
+    /// Role mention
+    Role(RoleId),
+    /// Channel mention
+    Channel(ChannelId),
+    /// Custom emoji
+    Emoji {
+        /// Emoji name
+        name: &'a str,
+        /// Emoji ID
+        id: Snowflake,
+        /// Whether the emoji is animated
+        animated: bool,
+    },
+    /// Timestamp tag
+    Timestamp {
+        /// Seconds since the Unix epoch
+        unix_seconds: i64,
+        /// Display style, or `None` for the default
+        style: Option<TimestampStyle>,
+    },
+}
+
+/// Split message content into text, mentions, custom emoji and timestamps.
+/// Tags escaped with a backslash are left as text.
+pub fn tokenize(content: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut search_from = 0;
+    while let Some(offset) = content[search_from..].find('<') {
+        let start = search_from + offset;
+        search_from = start + 1;
+        if content[..start].ends_with('\\') {
+            continue;
+        }
+        let end = match content[start..].find('>') {
+            Some(offset) => start + offset,
+            None => break,
+        };
+        if let Some(token) = parse_tag(&content[start + 1..end]) {
+            if text_start < start {
+                tokens.push(Token::Text(&content[text_start..start]));
+            }
+            tokens.push(token);
+            text_start = end + 1;
+            search_from = end + 1;
+        }
+    }
+    if text_start < content.len() {
+        tokens.push(Token::Text(&content[text_start..]));
+    }
+    tokens
+}
+
+/// Parse the inside of a `<...>` tag
+fn parse_tag(tag: &str) -> Option<Token<'_>> {
+    if let Some(id) = tag.strip_prefix("@&") {
+        parse_id(id).map(|id| Token::Role(RoleId(id)))
+    } else if let Some(id) = tag.strip_prefix("@!") {
+        parse_id(id).map(|id| Token::User(UserId(id)))
+    } else if let Some(id) = tag.strip_prefix('@') {
+        parse_id(id).map(|id| Token::User(UserId(id)))
+    } else if let Some(id) = tag.strip_prefix('#') {
+        parse_id(id).map(|id| Token::Channel(ChannelId(id)))
+    } else if let Some(rest) = tag.strip_prefix("t:") {
+        let mut parts = rest.splitn(2, ':');
+        let unix_seconds = parts.next()?.parse().ok()?;
+        let style = match parts.next() {
+            Some(flag) => {
+                let mut chars = flag.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(flag), None) => Some(TimestampStyle::from_flag(flag)?),
+                    _ => return None,
+                }
+            }
+            None => None,
+        };
+        Some(Token::Timestamp {
+            unix_seconds,
+            style,
+        })
+    } else {
+        let (animated, rest) = match tag.strip_prefix("a:") {
+            Some(rest) => (true, rest),
+            None => (false, tag.strip_prefix(':')?),
+        };
+        let mut parts = rest.splitn(2, ':');
+        let name = parts.next()?;
+        let id = parse_id(parts.next()?)?;
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+        Some(Token::Emoji { name, id, animated })
+    }
+}
+
+/// Parse a snowflake ID, rejecting signs and other characters `u64::from_str` would accept
+fn parse_id(id: &str) -> Option<Snowflake> {
+    if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+        id.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_markdown_escapes_formatting() {
+        assert_eq!(escape_markdown("*hi* _there_ `x`"), "\\*hi\\* \\_there\\_ \\`x\\`");
+        assert_eq!(escape_markdown("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn escape_mentions_breaks_tags_and_everyone() {
+        assert_eq!(escape_mentions("<@123> <#456>"), "\\<@123> \\<#456>");
+        assert_eq!(escape_mentions("<@&789>"), "\\<@&789>");
+        assert_eq!(escape_mentions("@everyone @here"), "@\u{200B}everyone @\u{200B}here");
+        assert_eq!(escape_mentions("a < b"), "a < b");
+    }
+
+    #[test]
+    fn escape_leaves_no_live_mentions() {
+        let escaped = escape("**<@123>** @everyone");
+        assert_eq!(escaped, "\\*\\*\\<@123\\>\\*\\* @\u{200B}everyone");
+        assert!(tokenize(&escaped)
+            .iter()
+            .all(|token| matches!(token, Token::Text(_))));
+    }
+
+    #[test]
+    fn inline_code_contains_backticks() {
+        assert_eq!(inline_code("a"), "`a`");
+        assert_eq!(inline_code("a`b"), "`` a`b ``");
+        let code = inline_code("a```b");
+        assert_eq!(code, "`` a`\u{200B}`\u{200B}`b ``");
+        assert!(!code[2..code.len() - 2].contains("``"));
+    }
+
+    #[test]
+    fn code_block_contains_backtick_runs() {
+        assert_eq!(code_block(Some("rs"), "x"), "```rs\nx\n```");
+        for run in 3..8 {
+            let code = format!("a{}b", "`".repeat(run));
+            let block = code_block(None, &code);
+            assert_eq!(block.matches("```").count(), 2, "{:?}", block);
+            assert!(!block[3..block.len() - 3].contains("``"));
+        }
+    }
+
+    #[test]
+    fn timestamp_formats_style() {
+        assert_eq!(timestamp(1618953630, None), "<t:1618953630>");
+        assert_eq!(
+            timestamp(1618953630, Some(TimestampStyle::Relative)),
+            "<t:1618953630:R>"
+        );
+    }
+
+    #[test]
+    fn tokenize_splits_mentions_from_text() {
+        assert_eq!(
+            tokenize("hi <@123>, see <#456> and <@&789>!"),
+            vec![
+                Token::Text("hi "),
+                Token::User(UserId(Snowflake(123))),
+                Token::Text(", see "),
+                Token::Channel(ChannelId(Snowflake(456))),
+                Token::Text(" and "),
+                Token::Role(RoleId(Snowflake(789))),
+                Token::Text("!"),
+            ]
+        );
+        assert_eq!(tokenize("<@!123>"), vec![Token::User(UserId(Snowflake(123)))]);
+    }
+
+    #[test]
+    fn tokenize_parses_emoji_and_timestamps() {
+        assert_eq!(
+            tokenize("<:wave:1><a:dance_2:2><t:1618953630><t:-5:R>"),
+            vec![
+                Token::Emoji {
+                    name: "wave",
+                    id: Snowflake(1),
+                    animated: false,
+                },
+                Token::Emoji {
+                    name: "dance_2",
+                    id: Snowflake(2),
+                    animated: true,
+                },
+                Token::Timestamp {
+                    unix_seconds: 1618953630,
+                    style: None,
+                },
+                Token::Timestamp {
+                    unix_seconds: -5,
+                    style: Some(TimestampStyle::Relative),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_leaves_invalid_and_escaped_tags_as_text() {
+        for text in &[
+            "<@+123>",
+            "<@12a>",
+            "<@>",
+            "<#-1>",
+            "<:bad name:1>",
+            "<t:1:X>",
+            "<t:1:RR>",
+            "\\<@123>",
+            "a < b > c",
+            "<@123",
+        ] {
+            assert_eq!(tokenize(text), vec![Token::Text(text)], "{:?}", text);
+        }
+        assert_eq!(tokenize(""), vec![]);
+    }
+
+    #[test]
+    fn tokenize_finds_tag_after_stray_bracket() {
+        assert_eq!(
+            tokenize("a <b <@1>"),
+            vec![Token::Text("a <b "), Token::User(UserId(Snowflake(1)))]
+        );
+    }
+
+    #[test]
+    fn parse_id_accepts_only_digits() {
+        assert_eq!(parse_id("18446744073709551615"), Some(Snowflake(u64::MAX)));
+        assert_eq!(parse_id("18446744073709551616"), None);
+        assert_eq!(parse_id("+1"), None);
+        assert_eq!(parse_id(" 1"), None);
+        assert_eq!(parse_id(""), None);
+    }
+}
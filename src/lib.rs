@@ -1,12 +1,20 @@
 //! Library for interacting with the Discord API and Gateway, especially for bots, using hyper/tokio.
+//!
+//! Diagnostics are emitted through [`tracing`](https://docs.rs/tracing), with a span for each gateway connection and REST request.
+//! If no tracing subscriber is installed, they are forwarded to the [`log`](https://docs.rs/log) crate instead.
 
 #![warn(missing_docs)]
 
+/// Re-exported for naming the [`Timestamp`](events/type.Timestamp.html) type
+pub extern crate chrono;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
+extern crate rand;
+extern crate serde;
 extern crate tokio;
 extern crate tokio_tungstenite;
+extern crate tracing_futures;
 extern crate url;
 #[macro_use]
 extern crate serde_json;
@@ -16,6 +24,8 @@ extern crate serde_derive;
 extern crate try_future;
 #[macro_use]
 extern crate quick_error;
+#[macro_use]
+extern crate tracing;
 
 /// Objects for sending messages
 pub mod builder;
@@ -23,10 +33,12 @@ mod client;
 mod error;
 /// Events and related objects
 pub mod events;
+/// Helpers for escaping and formatting Discord markdown
+pub mod fmt;
 
-pub use builder::{EmbedBuilder, MessageBuilder};
-pub use client::{Client, GatewayConnection};
-pub use error::Error;
+pub use builder::{EmbedBuilder, MessageBuilder, WebhookMessageBuilder};
+pub use client::{Client, GatewayConnection, WebhookClient};
+pub use error::{Error, LimitError};
 pub use events::Event;
 
 #[derive(Deserialize, Serialize)]